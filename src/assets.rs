@@ -1,4 +1,6 @@
 use crate::asset_tracking::LoadResource;
+use crate::parrylord::ai::Brain;
+use crate::parrylord::animation::SpriteAnimation;
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
@@ -23,13 +25,22 @@ pub fn plugin(app: &mut App) {
 #[reflect(Resource)]
 pub struct EnemyAssets {
     #[dependency]
-    pub beige: Handle<Image>,
+    pub beige: Handle<SpriteAnimation>,
     #[dependency]
-    pub blue: Handle<Image>,
+    pub blue: Handle<SpriteAnimation>,
     #[dependency]
-    pub green: Handle<Image>,
+    pub green: Handle<SpriteAnimation>,
     #[dependency]
-    pub yellow: Handle<Image>,
+    pub yellow: Handle<SpriteAnimation>,
+
+    #[dependency]
+    pub beige_brain: Handle<Brain>,
+    #[dependency]
+    pub blue_brain: Handle<Brain>,
+    #[dependency]
+    pub green_brain: Handle<Brain>,
+    #[dependency]
+    pub yellow_brain: Handle<Brain>,
 }
 
 impl EnemyAssets {
@@ -41,10 +52,14 @@ impl FromWorld for EnemyAssets {
         let assets = world.resource::<AssetServer>();
 
         Self {
-            beige: assets.load("images/enemy/beige.png"),
-            blue: assets.load("images/enemy/blue.png"),
-            green: assets.load("images/enemy/green.png"),
-            yellow: assets.load("images/enemy/yellow.png"),
+            beige: assets.load("animations/enemy_beige.anim.json"),
+            blue: assets.load("animations/enemy_blue.anim.json"),
+            green: assets.load("animations/enemy_green.anim.json"),
+            yellow: assets.load("animations/enemy_yellow.anim.json"),
+            beige_brain: assets.load("ai/enemy_beige.ai.json"),
+            blue_brain: assets.load("ai/enemy_blue.ai.json"),
+            green_brain: assets.load("ai/enemy_green.ai.json"),
+            yellow_brain: assets.load("ai/enemy_yellow.ai.json"),
         }
     }
 }
@@ -53,15 +68,7 @@ impl FromWorld for EnemyAssets {
 #[reflect(Resource)]
 pub struct PlayerAssets {
     #[dependency]
-    pub front2: Handle<Image>,
-    #[dependency]
-    pub front1: Handle<Image>,
-    #[dependency]
-    pub walk1: Handle<Image>,
-    #[dependency]
-    pub walk2: Handle<Image>,
-    #[dependency]
-    pub stand: Handle<Image>,
+    pub animation: Handle<SpriteAnimation>,
     #[dependency]
     pub attack_indicator: Handle<Image>,
 
@@ -73,11 +80,7 @@ impl FromWorld for PlayerAssets {
     fn from_world(world: &mut World) -> Self {
         let assets = world.resource::<AssetServer>();
         Self {
-            front1: assets.load("images/pink/front1.png"),
-            front2: assets.load("images/pink/front2.png"),
-            walk1: assets.load("images/pink/walk1.png"),
-            walk2: assets.load("images/pink/walk2.png"),
-            stand: assets.load("images/pink/stand.png"),
+            animation: assets.load("animations/player.anim.json"),
             attack_indicator: assets.load("images/pink/attack_indicator.png"),
 
             hurt_sfx: vec![
@@ -94,30 +97,10 @@ impl FromWorld for PlayerAssets {
 #[derive(Resource, Asset, Clone, Reflect, Debug)]
 #[reflect(Resource)]
 pub struct AttackAssets {
+    /// Every projectile frame, in a grid of [`Self::FRAME_SIZE`] cells.
     #[dependency]
-    pub _0: Handle<Image>,
-    #[dependency]
-    pub _1: Handle<Image>,
-    #[dependency]
-    pub _2: Handle<Image>,
-    #[dependency]
-    pub _3: Handle<Image>,
-    #[dependency]
-    pub _4: Handle<Image>,
-    #[dependency]
-    pub _5: Handle<Image>,
-    #[dependency]
-    pub _6: Handle<Image>,
-    #[dependency]
-    pub _7: Handle<Image>,
-    #[dependency]
-    pub _8: Handle<Image>,
-    #[dependency]
-    pub _9: Handle<Image>,
-    #[dependency]
-    pub _10: Handle<Image>,
-    #[dependency]
-    pub _11: Handle<Image>,
+    pub sheet: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
     #[dependency]
     pub parry_sfx: Vec<Handle<AudioSource>>,
     #[dependency]
@@ -125,26 +108,39 @@ pub struct AttackAssets {
 }
 
 impl AttackAssets {
-    pub const MAX: u8 = 12;
+    pub const FRAME_SIZE: u32 = 512;
+    pub const FRAMES: usize = 12;
+    const COLUMNS: u32 = 4;
+
+    /// A sprite showing `frame` of the sheet, wrapping around past the last one.
+    pub fn sprite(&self, frame: usize) -> Sprite {
+        Sprite::from_atlas_image(
+            self.sheet.clone(),
+            TextureAtlas {
+                layout: self.layout.clone(),
+                index: frame % Self::FRAMES,
+            },
+        )
+    }
 }
 
 impl FromWorld for AttackAssets {
     fn from_world(world: &mut World) -> Self {
+        let layout =
+            world
+                .resource_mut::<Assets<TextureAtlasLayout>>()
+                .add(TextureAtlasLayout::from_grid(
+                    UVec2::splat(Self::FRAME_SIZE),
+                    Self::COLUMNS,
+                    (Self::FRAMES as u32).div_ceil(Self::COLUMNS),
+                    None,
+                    None,
+                ));
         let assets = world.resource::<AssetServer>();
 
         Self {
-            _0: assets.load("images/attack/0.png"),
-            _1: assets.load("images/attack/1.png"),
-            _2: assets.load("images/attack/2.png"),
-            _3: assets.load("images/attack/3.png"),
-            _4: assets.load("images/attack/4.png"),
-            _5: assets.load("images/attack/5.png"),
-            _6: assets.load("images/attack/6.png"),
-            _7: assets.load("images/attack/7.png"),
-            _8: assets.load("images/attack/8.png"),
-            _9: assets.load("images/attack/9.png"),
-            _10: assets.load("images/attack/10.png"),
-            _11: assets.load("images/attack/11.png"),
+            sheet: assets.load("images/attack/sheet.png"),
+            layout,
             parry_sfx: vec![
                 assets.load("audio/sound_effects/parry_0.ogg"),
                 assets.load("audio/sound_effects/parry_1.ogg"),
@@ -0,0 +1,302 @@
+//! Command-line options for native builds.
+//!
+//! These configure the matching resources before [`Startup`], e.g. to jump straight into a late
+//! level or to reproduce a specific seed.
+
+use crate::leaderboard::{HighScore, LeaderboardConfig};
+use crate::parrylord::benchmark::{Benchmark, BENCHMARK_LEVEL};
+use crate::parrylord::level::StartingLevel;
+use crate::parrylord::mode::GameMode;
+use crate::parrylord::projectile::ProjectileConfig;
+use crate::parrylord::replay::{load_replay, Replay, ReplayFinished, ReplayOutput, ReplayPlayback};
+use crate::parrylord::rng::FixedSeed;
+use crate::screens::Screen;
+use crate::ParrylordSingleton;
+use bevy::diagnostic::FrameCount;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use clap::{ArgGroup, Parser};
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Parser, Debug, Clone, Default)]
+#[command(version, about)]
+// Everything that runs without a window, for `--frames` to require.
+#[command(group(
+    ArgGroup::new("headless_run")
+        .args(["headless", "verify", "benchmark"])
+        .multiple(true)
+))]
+pub struct Cli {
+    /// Seed the gameplay RNG of every run with this instead of a random seed.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Start every run at this level.
+    #[arg(long, value_name = "LEVEL")]
+    pub start_level: Option<u32>,
+    /// The rule set to play with.
+    #[arg(long, value_enum)]
+    pub mode: Option<GameMode>,
+    /// Go straight to the title screen.
+    #[arg(long)]
+    pub skip_splash: bool,
+    /// Play back a recorded run instead of reading the keyboard and mouse.
+    ///
+    /// The replay's seed, mode and starting level take precedence over the other options.
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+    /// Save the replay of every finished run to this file.
+    #[arg(long, value_name = "FILE")]
+    pub record_replay: Option<PathBuf>,
+    /// Run without a window, starting gameplay right away with a fixed frame time.
+    ///
+    /// The app exits when the run (or the replay) ends.
+    #[arg(long)]
+    pub headless: bool,
+    /// Exit after this many frames.
+    #[arg(long, value_name = "N", requires = "headless_run")]
+    pub frames: Option<u32>,
+    /// Base URL of the high score worker.
+    #[arg(long, value_name = "URL")]
+    pub leaderboard_url: Option<String>,
+    /// Don't talk to the leaderboard at all.
+    #[arg(long, conflicts_with = "leaderboard_url")]
+    pub offline: bool,
+    /// Check that a high score submission (as JSON) reproduces by playing back its replay.
+    ///
+    /// Implies `--headless`. Exits with an error if the replay doesn't reach the submitted score.
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub verify: Option<PathBuf>,
+    /// How many attacks can be on screen at once. Past this, the oldest fade out.
+    #[arg(long, value_name = "N")]
+    pub max_projectiles: Option<usize>,
+    /// Measure frame times in a busy late level, with the player parrying automatically.
+    ///
+    /// Implies `--headless`. Starts at level 30 with seed 0, unless told otherwise.
+    #[arg(
+        long,
+        value_name = "FRAMES",
+        num_args = 0..=1,
+        default_missing_value = "1800",
+        conflicts_with_all = ["replay", "verify"]
+    )]
+    pub benchmark: Option<usize>,
+}
+
+impl Cli {
+    pub const fn headless(&self) -> bool {
+        self.headless || self.verify.is_some() || self.benchmark.is_some()
+    }
+}
+
+impl Plugin for Cli {
+    fn build(&self, app: &mut App) {
+        if let Some(frames) = self.benchmark {
+            // Enough for the enemies to get their attacks going.
+            app.insert_resource(Benchmark::new(300, frames));
+            app.insert_resource(FixedSeed(Some(0)));
+            app.insert_resource(StartingLevel(BENCHMARK_LEVEL));
+        }
+
+        if let Some(seed) = self.seed {
+            app.insert_resource(FixedSeed(Some(seed)));
+        }
+
+        if let Some(level) = self.start_level {
+            app.insert_resource(StartingLevel(level));
+        }
+
+        if let Some(max_projectiles) = self.max_projectiles {
+            app.insert_resource(ProjectileConfig { max_projectiles });
+        }
+
+        if let Some(mode) = self.mode {
+            app.insert_resource(mode);
+        }
+
+        if let Some(path) = &self.replay {
+            match load_replay(path) {
+                Ok(replay) => play_back(app, replay),
+                Err(e) => {
+                    error!("couldn't load replay {}: {e}", path.display());
+                    app.add_systems(Startup, |mut app_exit: EventWriter<AppExit>| {
+                        app_exit.write(AppExit::error());
+                    });
+                }
+            }
+        }
+
+        if let Some(path) = &self.verify {
+            match load_submission(path) {
+                Ok((submission, replay)) => {
+                    play_back(app, replay);
+                    app.insert_resource(Verification(submission));
+                }
+                Err(e) => {
+                    error!("couldn't load submission {}: {e}", path.display());
+                    app.add_systems(Startup, |mut app_exit: EventWriter<AppExit>| {
+                        app_exit.write(AppExit::error());
+                    });
+                }
+            }
+        }
+
+        if let Some(path) = &self.record_replay {
+            app.insert_resource(ReplayOutput(path.clone()));
+        }
+
+        if let Some(url) = &self.leaderboard_url {
+            app.insert_resource(LeaderboardConfig::online(url));
+        } else if self.offline {
+            app.insert_resource(LeaderboardConfig::OFFLINE);
+        }
+
+        if self.headless() {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+                1.0 / 60.0,
+            )));
+            app.insert_resource(Headless {
+                frames: self.frames,
+            });
+            app.add_systems(Startup, enter_loading_screen);
+            app.add_systems(
+                Last,
+                exit_headless_run.run_if(headless_run_over.or(on_event::<ReplayFinished>)),
+            );
+            app.add_systems(OnExit(Screen::Gameplay), exit_headless_run);
+        } else if self.skip_splash {
+            app.add_systems(Startup, enter_title_screen);
+        }
+    }
+}
+
+fn play_back(app: &mut App, replay: Replay) {
+    app.insert_resource(FixedSeed(Some(replay.seed)));
+    app.insert_resource(StartingLevel(replay.start_level));
+    app.insert_resource(replay.mode);
+    app.insert_resource(ReplayPlayback::new(replay));
+}
+
+fn load_submission(path: &std::path::Path) -> std::io::Result<(HighScore, Replay)> {
+    let file = std::fs::File::open(path).map(std::io::BufReader::new)?;
+    let submission: HighScore = serde_json::from_reader(file)?;
+
+    let Some(compressed) = &submission.replay else {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the submission has no replay",
+        ));
+    };
+    let replay = Replay::decompress(compressed)?;
+
+    if submission
+        .run
+        .as_ref()
+        .is_some_and(|run| run.seed != replay.seed || run.mode != replay.mode)
+    {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "the replay is from a different run",
+        ));
+    }
+
+    Ok((submission, replay))
+}
+
+/// The submission being verified with `--verify`.
+#[derive(Resource, Debug, Clone)]
+struct Verification(HighScore);
+
+impl Verification {
+    /// Compares the submission with the run its replay reproduced, returning what doesn't match.
+    fn mismatches(&self, singleton: &ParrylordSingleton) -> Vec<String> {
+        let Self(submission) = self;
+        let mut mismatches = Vec::new();
+
+        let score = singleton.calculate_score();
+        if submission.score != score {
+            mismatches.push(format!(
+                "score: submitted {}, replayed {score}",
+                submission.score
+            ));
+        }
+
+        if let Some(run) = &submission.run {
+            let ParrylordSingleton {
+                enemies_killed,
+                level,
+                max_parried,
+                volleys_cleared,
+                longest_rally,
+            } = *singleton;
+
+            for (field, submitted, replayed) in [
+                ("enemies killed", run.enemies_killed, enemies_killed),
+                ("level", run.level, level),
+                ("max parried", run.max_parried, max_parried),
+                ("volleys cleared", run.volleys_cleared, volleys_cleared),
+                ("longest rally", run.longest_rally, longest_rally),
+            ] {
+                if submitted != replayed {
+                    mismatches.push(format!(
+                        "{field}: submitted {submitted}, replayed {replayed}"
+                    ));
+                }
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// Running from the command line without a window: `--headless`, `--verify` or `--benchmark`.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct Headless {
+    /// How many frames to run for, if limited.
+    frames: Option<u32>,
+}
+
+fn enter_title_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
+
+fn enter_loading_screen(mut next_screen: ResMut<NextState<Screen>>) {
+    // The loading screen moves on to gameplay as soon as everything is loaded.
+    next_screen.set(Screen::Loading);
+}
+
+fn headless_run_over(headless: Res<Headless>, frame_count: Res<FrameCount>) -> bool {
+    headless
+        .frames
+        .is_some_and(|frames| frame_count.0 >= frames)
+}
+
+fn exit_headless_run(
+    singleton: Res<ParrylordSingleton>,
+    frame_count: Res<FrameCount>,
+    verification: Option<Res<Verification>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    info!(
+        frames = frame_count.0,
+        ?singleton,
+        score = singleton.calculate_score(),
+        "headless run over"
+    );
+
+    let Some(verification) = verification else {
+        app_exit.write(AppExit::Success);
+        return;
+    };
+
+    let mismatches = verification.mismatches(&singleton);
+    if mismatches.is_empty() {
+        info!("verified score {}", verification.0.score);
+        app_exit.write(AppExit::Success);
+    } else {
+        for mismatch in mismatches {
+            error!("verification failed, {mismatch}");
+        }
+        app_exit.write(AppExit::error());
+    }
+}
@@ -1,5 +1,7 @@
 //! Development tools for the game. This plugin is only enabled in dev builds.
 
+use crate::parrylord::ai::Thinker;
+use crate::parrylord::enemy::Enemy;
 use crate::screens::Screen;
 use avian2d::prelude::PhysicsDebugPlugin;
 use bevy::{
@@ -25,10 +27,92 @@ pub fn plugin(app: &mut App) {
     .add_plugins(WorldInspectorPlugin::default());
 
     app.add_plugins(PhysicsDebugPlugin::default());
+
+    // Toggle labels showing what each enemy is thinking.
+    app.init_resource::<AiOverlay>();
+    app.add_systems(
+        Update,
+        (
+            toggle_ai_overlay.run_if(input_just_pressed(AI_OVERLAY_KEY)),
+            show_ai_overlay,
+        )
+            .chain(),
+    );
 }
 
 const TOGGLE_KEY: KeyCode = KeyCode::Backquote;
+const AI_OVERLAY_KEY: KeyCode = KeyCode::F3;
 
 fn toggle_debug_ui(mut options: ResMut<UiDebugOptions>) {
     options.toggle();
 }
+
+#[derive(Resource, Debug, Default)]
+struct AiOverlay(bool);
+
+#[derive(Component, Debug)]
+struct AiLabel;
+
+fn toggle_ai_overlay(
+    mut overlay: ResMut<AiOverlay>,
+    labels: Query<Entity, With<AiLabel>>,
+    mut commands: Commands,
+) {
+    overlay.0 = !overlay.0;
+    if !overlay.0 {
+        for label in &labels {
+            commands.entity(label).despawn();
+        }
+    }
+}
+
+fn show_ai_overlay(
+    overlay: Res<AiOverlay>,
+    thinkers: Query<(Entity, &Enemy, &Thinker, Option<&Children>)>,
+    mut labels: Query<&mut Text2d, With<AiLabel>>,
+    mut commands: Commands,
+) {
+    if !overlay.0 {
+        return;
+    }
+
+    for (entity, enemy, thinker, children) in &thinkers {
+        // The state it's in, then what it last picked from, best first.
+        let mut scores = thinker.scores.clone();
+        scores.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        let mut text = format!("{enemy:?}");
+        for (action, score) in scores {
+            let marker = if Some(action) == thinker.thought {
+                ">"
+            } else {
+                " "
+            };
+            text += &format!("\n{marker} {action:?} {score:.2}");
+        }
+
+        let label = children
+            .into_iter()
+            .flatten()
+            .find(|&&child| labels.contains(child));
+        match label {
+            Some(&label) => {
+                if let Ok(mut label) = labels.get_mut(label) {
+                    label.0 = text;
+                }
+            }
+            None => {
+                commands.entity(entity).with_child((
+                    Name::new("AiLabel"),
+                    AiLabel,
+                    Text2d::new(text),
+                    TextFont {
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                    Transform::from_xyz(0.0, 140.0, 10.0),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+//! The runs played on this machine, and the best one of each mode.
+//!
+//! Unlike [`HighScores`](super::HighScores), this works without a network connection. It's saved
+//! to disk on native builds.
+
+#[cfg(not(target_family = "wasm"))]
+use crate::cli::Headless;
+use crate::parrylord::mode::GameMode;
+use crate::parrylord::rng::RunSeed;
+use crate::screens::Screen;
+use crate::ParrylordSingleton;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.insert_resource(RunHistory::load());
+    // Headless runs are for testing, verifying and benchmarking, not the player's own.
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(
+        OnEnter(Screen::HighScore),
+        record_run.run_if(not(resource_exists::<Headless>)),
+    );
+    #[cfg(target_family = "wasm")]
+    app.add_systems(OnEnter(Screen::HighScore), record_run);
+}
+
+/// Where the history is saved between launches.
+#[cfg(not(target_family = "wasm"))]
+const HISTORY_PATH: &str = "history.json";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RunRecord {
+    /// When the run ended, in seconds since the Unix epoch, if the clock is available.
+    pub date: Option<u64>,
+    pub seed: u64,
+    pub mode: GameMode,
+    pub score: u128,
+    pub enemies_killed: u32,
+    pub level: u32,
+    pub max_parried: u32,
+    #[serde(default)]
+    pub volleys_cleared: u32,
+    #[serde(default)]
+    pub longest_rally: u32,
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RunHistory {
+    /// The latest runs, oldest first.
+    pub runs: Vec<RunRecord>,
+    /// The best run of each mode.
+    pub personal_bests: Vec<RunRecord>,
+    /// Whether the latest run beat the personal best of its mode.
+    #[serde(skip)]
+    pub new_personal_best: bool,
+}
+
+impl RunHistory {
+    /// How many runs are kept.
+    pub const MAX_RUNS: usize = 50;
+
+    #[cfg(not(target_family = "wasm"))]
+    fn load() -> Self {
+        match std::fs::read(HISTORY_PATH) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!("couldn't read the run history {HISTORY_PATH}: {e}");
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn load() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn save(&self) {
+        let result = serde_json::to_vec(self)
+            .map_err(std::io::Error::from)
+            .and_then(|json| std::fs::write(HISTORY_PATH, json));
+
+        if let Err(e) = result {
+            error!(?e);
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    #[expect(clippy::unused_self)]
+    const fn save(&self) {}
+
+    pub fn personal_best(&self, mode: GameMode) -> Option<&RunRecord> {
+        self.personal_bests.iter().find(|it| it.mode == mode)
+    }
+
+    /// The best runs that are still in the history, best first.
+    pub fn top_runs(&self) -> Vec<&RunRecord> {
+        let mut runs = self.runs.iter().collect::<Vec<_>>();
+        runs.sort_by(|a, b| b.score.cmp(&a.score));
+        runs
+    }
+
+    pub fn push(&mut self, run: RunRecord) {
+        self.new_personal_best = self
+            .personal_best(run.mode)
+            .is_none_or(|best| run.score > best.score);
+
+        if self.new_personal_best {
+            self.personal_bests.retain(|it| it.mode != run.mode);
+            self.personal_bests.push(run.clone());
+        }
+
+        self.runs.push(run);
+        let excess = self.runs.len().saturating_sub(Self::MAX_RUNS);
+        self.runs.drain(..excess);
+
+        self.save();
+    }
+}
+
+fn record_run(
+    mut history: ResMut<RunHistory>,
+    singleton: Res<ParrylordSingleton>,
+    mode: Res<GameMode>,
+    seed: Res<RunSeed>,
+) {
+    let ParrylordSingleton {
+        enemies_killed,
+        level,
+        max_parried,
+        volleys_cleared,
+        longest_rally,
+    } = *singleton;
+
+    history.push(RunRecord {
+        date: now(),
+        seed: seed.0,
+        mode: *mode,
+        score: singleton.calculate_score(),
+        enemies_killed,
+        level,
+        max_parried,
+        volleys_cleared,
+        longest_rally,
+    });
+}
+
+/// The current time in seconds since the Unix epoch.
+#[cfg(not(target_family = "wasm"))]
+pub fn now() -> Option<u64> {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|it| it.as_secs())
+}
+
+/// `SystemTime` isn't available on the web.
+#[cfg(target_family = "wasm")]
+pub const fn now() -> Option<u64> {
+    None
+}
+
+/// Formats seconds since the Unix epoch as a `YYYY-MM-DD` date (in UTC).
+pub fn format_date(secs: u64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = (secs / 86_400).cast_signed() + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
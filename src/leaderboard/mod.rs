@@ -0,0 +1,634 @@
+//! The leaderboard: where high scores are fetched from and submitted to.
+//!
+//! Anything that wants to talk to the leaderboard writes a [`LeaderboardRequest`]. The active
+//! [`LeaderboardClient`] handles it, and reports back by triggering a [`LeaderboardResponse`],
+//! which is also reflected in [`LeaderboardStatus`] for the UI.
+//!
+//! The URL comes from (in order of precedence) the `--leaderboard-url` flag, the
+//! `PARRYLORD_LEADERBOARD_URL` environment variable, the same variable at build time, and finally
+//! [`CF_WORKER_URL`]. Setting `PARRYLORD_OFFLINE` (or passing `--offline`) disables every network
+//! call and uses a [`LocalLeaderboard`] instead.
+//!
+//! Scores go through the [`outbox::Outbox`], which retries them until they're submitted.
+
+use crate::parrylord::mode::GameMode;
+use crate::ParrylordSingleton;
+use bevy::ecs::system::SystemState;
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use bevy_mod_reqwest::{reqwest, BevyReqwest, ReqwestErrorEvent, ReqwestResponseEvent};
+use std::fmt::{Display, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub mod history;
+pub mod outbox;
+
+pub fn plugin(app: &mut App) {
+    app.add_plugins((history::plugin, outbox::plugin));
+
+    app.register_type::<LeaderboardConfig>();
+    app.init_resource::<LeaderboardConfig>();
+    app.init_resource::<HighScores>();
+    app.init_resource::<LeaderboardStatus>();
+    app.add_event::<LeaderboardRequest>();
+    app.add_observer(apply_leaderboard_response);
+
+    app.add_systems(
+        PreUpdate,
+        create_leaderboard_client.run_if(resource_changed::<LeaderboardConfig>),
+    );
+    app.add_systems(
+        Update,
+        request_high_scores.run_if(on_timer(Duration::from_secs_f32(10.0))),
+    );
+    // Requests are handled after everything in `Update` could send them, so the
+    // `LeaderboardStatus` is up to date by the next frame.
+    app.add_systems(PostUpdate, handle_leaderboard_requests);
+}
+
+pub const CF_WORKER_URL: &str = "https://parrylord-high-score-worker.pawarherschel.workers.dev/";
+
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct LeaderboardConfig {
+    /// Base URL of the high score worker, or `None` when offline.
+    pub url: Option<String>,
+}
+
+impl LeaderboardConfig {
+    pub const OFFLINE: Self = Self { url: None };
+
+    pub fn online(url: impl Into<String>) -> Self {
+        Self {
+            url: Some(url.into()),
+        }
+    }
+}
+
+impl Default for LeaderboardConfig {
+    fn default() -> Self {
+        let offline = runtime_var("PARRYLORD_OFFLINE")
+            .or_else(|| option_env!("PARRYLORD_OFFLINE").map(String::from))
+            .is_some_and(|it| !matches!(it.as_str(), "" | "0" | "false"));
+
+        if offline {
+            return Self::OFFLINE;
+        }
+
+        let url = runtime_var("PARRYLORD_LEADERBOARD_URL")
+            .or_else(|| option_env!("PARRYLORD_LEADERBOARD_URL").map(String::from))
+            .filter(|it| !it.is_empty())
+            .unwrap_or_else(|| String::from(CF_WORKER_URL));
+
+        Self::online(url)
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn runtime_var(key: &str) -> Option<String> {
+    std::env::var(key).ok()
+}
+
+#[cfg(target_family = "wasm")]
+fn runtime_var(_key: &str) -> Option<String> {
+    None
+}
+
+/// The version of the [`HighScore`] format this build submits.
+///
+/// Version 1 only had a name and a score, version 2 added [`RunMetadata`], version 3 the replay,
+/// version 4 the date, version 5 the volleys cleared, and version 6 the longest rally.
+pub const SCHEMA_VERSION: u32 = 6;
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct HighScore {
+    pub name: String,
+    pub score: u128,
+    #[serde(default = "legacy_schema")]
+    pub schema: u32,
+    /// Details of the run the score is from. Missing for scores submitted by older versions, and
+    /// for anything a newer version sends that this one can't read.
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub run: Option<RunMetadata>,
+    /// When the score was submitted, in seconds since the Unix epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<u64>,
+    /// The run's compressed [`Replay`](crate::parrylord::replay::Replay), so the score can be verified by playing it back.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay: Option<String>,
+}
+
+impl HighScore {
+    pub fn new(name: String, run: RunMetadata, replay: Option<String>) -> Self {
+        Self {
+            name,
+            score: run.score(),
+            schema: SCHEMA_VERSION,
+            run: Some(run),
+            date: history::now(),
+            replay,
+        }
+    }
+}
+
+const fn legacy_schema() -> u32 {
+    1
+}
+
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RunMetadata {
+    pub game_version: String,
+    pub mode: GameMode,
+    pub seed: u64,
+    pub level: u32,
+    pub enemies_killed: u32,
+    pub max_parried: u32,
+    #[serde(default)]
+    pub volleys_cleared: u32,
+    #[serde(default)]
+    pub longest_rally: u32,
+    pub duration_ms: u64,
+}
+
+impl RunMetadata {
+    pub fn new(
+        singleton: &ParrylordSingleton,
+        mode: GameMode,
+        seed: u64,
+        duration: Duration,
+    ) -> Self {
+        let &ParrylordSingleton {
+            enemies_killed,
+            level,
+            max_parried,
+            volleys_cleared,
+            longest_rally,
+        } = singleton;
+
+        Self {
+            game_version: String::from(env!("CARGO_PKG_VERSION")),
+            mode,
+            seed,
+            level,
+            enemies_killed,
+            max_parried,
+            volleys_cleared,
+            longest_rally,
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+
+    const fn score(&self) -> u128 {
+        ParrylordSingleton {
+            enemies_killed: self.enemies_killed,
+            level: self.level,
+            max_parried: self.max_parried,
+            volleys_cleared: self.volleys_cleared,
+            longest_rally: self.longest_rally,
+        }
+        .calculate_score()
+    }
+}
+
+/// Deserializes `T`, or `None` if the value doesn't fit.
+fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned,
+{
+    let value = <Option<serde_json::Value> as serde::Deserialize>::deserialize(deserializer)?;
+    Ok(value.and_then(|it| serde_json::from_value(it).ok()))
+}
+
+/// How many scores are fetched at a time.
+pub const PAGE_SIZE: usize = 20;
+
+/// The top scores fetched so far, best first.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct HighScores {
+    pub scores: Vec<HighScore>,
+    /// Whether there are no more scores to fetch.
+    pub complete: bool,
+}
+
+impl HighScores {
+    /// The page to fetch to see more scores, if there are any.
+    pub const fn next_page(&self) -> Option<usize> {
+        if self.complete {
+            None
+        } else {
+            Some(self.scores.len() / PAGE_SIZE)
+        }
+    }
+
+    /// Replaces the scores of `page` with the fetched ones.
+    fn apply_page(&mut self, page: usize, scores: Vec<HighScore>) {
+        // Older workers don't paginate, and always respond with every score.
+        if scores.len() > PAGE_SIZE {
+            self.scores = scores;
+            self.complete = true;
+            return;
+        }
+
+        let start = (page * PAGE_SIZE).min(self.scores.len());
+
+        // Older workers that ignore the offset respond with the first page every time. Without
+        // anything new, there's nothing more to fetch.
+        if start > 0 && scores.iter().all(|it| self.scores[..start].contains(it)) {
+            self.scores.truncate(start);
+            self.complete = true;
+            return;
+        }
+
+        let end = (start + PAGE_SIZE).min(self.scores.len());
+        let last_page = scores.len() < PAGE_SIZE;
+        let fetched = scores.len();
+
+        self.scores.splice(start..end, scores);
+
+        if last_page {
+            self.scores.truncate(start + fetched);
+            self.complete = true;
+        } else if end == self.scores.len() {
+            self.complete = false;
+        }
+    }
+}
+
+/// Reads the high scores the worker responded with.
+///
+/// The list may be wrapped in an object as `{ "scores": [...] }`. Entries that can't be read are
+/// skipped instead of failing the whole list.
+fn decode_high_scores(body: &[u8]) -> Result<Vec<HighScore>, LeaderboardError> {
+    let value = serde_json::from_slice::<serde_json::Value>(body)
+        .map_err(|e| LeaderboardError::Decode(e.to_string()))?;
+
+    let entries = match value {
+        serde_json::Value::Array(entries) => entries,
+        serde_json::Value::Object(mut object) => match object.remove("scores") {
+            Some(serde_json::Value::Array(entries)) => entries,
+            _ => {
+                return Err(LeaderboardError::Decode(String::from(
+                    "no scores in response",
+                )))
+            }
+        },
+        _ => {
+            return Err(LeaderboardError::Decode(String::from(
+                "unexpected response",
+            )))
+        }
+    };
+
+    Ok(entries
+        .into_iter()
+        .filter_map(|entry| {
+            serde_json::from_value(entry)
+                .inspect_err(|e| warn!("skipping unreadable high score: {e}"))
+                .ok()
+        })
+        .collect())
+}
+
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum LeaderboardRequest {
+    /// Fetch a page of [`PAGE_SIZE`] scores.
+    Fetch {
+        page: usize,
+    },
+    Submit(HighScore),
+    HealthCheck,
+}
+
+/// The outcome of a [`LeaderboardRequest`], triggered as a global observer event.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum LeaderboardResponse {
+    Fetched {
+        page: usize,
+        result: Result<Vec<HighScore>, LeaderboardError>,
+    },
+    Submitted(Result<(), LeaderboardError>),
+    HealthChecked(Result<(), LeaderboardError>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaderboardError {
+    /// The request never got a response.
+    Network(String),
+    /// The worker responded with a non-success status code.
+    Status(u16),
+    /// The response couldn't be understood.
+    Decode(String),
+    /// Reading or writing a local leaderboard failed.
+    Io(String),
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Network(e) => write!(f, "network error: {e}"),
+            Self::Status(code) => write!(f, "the leaderboard responded with status {code}"),
+            Self::Decode(e) => write!(f, "couldn't read the response: {e}"),
+            Self::Io(e) => write!(f, "couldn't access the local leaderboard: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LeaderboardError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum RequestState {
+    #[default]
+    Idle,
+    Loading,
+    Success,
+    Failed(LeaderboardError),
+}
+
+/// The state of the latest request of each kind.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LeaderboardStatus {
+    pub fetch: RequestState,
+    pub submit: RequestState,
+    pub health: RequestState,
+}
+
+/// A leaderboard backend.
+///
+/// Every operation finishes by triggering a [`LeaderboardResponse`], either right away or once a
+/// request completes.
+pub trait LeaderboardClient: Send + Sync + 'static {
+    /// Fetches the scores from `page * PAGE_SIZE` on, best first.
+    fn fetch(&self, page: usize, commands: &mut Commands);
+    fn submit(&self, score: HighScore, commands: &mut Commands);
+    fn health_check(&self, commands: &mut Commands);
+}
+
+/// The active [`LeaderboardClient`], created from the [`LeaderboardConfig`].
+#[derive(Resource, Clone, Deref)]
+pub struct Leaderboard(pub Arc<dyn LeaderboardClient>);
+
+/// Talks to the high score worker over HTTP.
+pub struct HttpLeaderboard {
+    pub url: String,
+}
+
+impl HttpLeaderboard {
+    /// Sends a request built by `build`, and turns the response (or the error) into a
+    /// [`LeaderboardResponse`] with `respond`.
+    fn send(
+        &self,
+        commands: &mut Commands,
+        build: impl FnOnce(&reqwest::Client, &str) -> reqwest::RequestBuilder + Send + 'static,
+        respond: impl Fn(Result<&ReqwestResponseEvent, LeaderboardError>) -> LeaderboardResponse
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        let url = self.url.clone();
+
+        commands.queue(move |world: &mut World| {
+            let mut state = SystemState::<BevyReqwest>::new(world);
+            let mut client = state.get_mut(world);
+
+            match build(client.client(), &url).build() {
+                Ok(request) => {
+                    let on_error = respond.clone();
+                    client
+                        .send(request)
+                        .on_response(
+                            move |trigger: Trigger<ReqwestResponseEvent>,
+                                  mut commands: Commands| {
+                                let response = trigger.event();
+                                commands.trigger(if response.status().is_success() {
+                                    respond(Ok(response))
+                                } else {
+                                    respond(Err(LeaderboardError::Status(
+                                        response.status().as_u16(),
+                                    )))
+                                });
+                            },
+                        )
+                        .on_error(
+                            move |trigger: Trigger<ReqwestErrorEvent>, mut commands: Commands| {
+                                let e = &trigger.event().0;
+                                commands.trigger(on_error(Err(LeaderboardError::Network(
+                                    e.to_string(),
+                                ))));
+                            },
+                        );
+                }
+                Err(e) => {
+                    world.trigger(respond(Err(LeaderboardError::Network(e.to_string()))));
+                }
+            }
+
+            state.apply(world);
+        });
+    }
+}
+
+impl LeaderboardClient for HttpLeaderboard {
+    fn fetch(&self, page: usize, commands: &mut Commands) {
+        self.send(
+            commands,
+            move |client, url| {
+                client
+                    .get(url)
+                    .query(&[("offset", page * PAGE_SIZE), ("limit", PAGE_SIZE)])
+            },
+            move |response| LeaderboardResponse::Fetched {
+                page,
+                result: response.and_then(|response| decode_high_scores(response.body())),
+            },
+        );
+    }
+
+    fn submit(&self, score: HighScore, commands: &mut Commands) {
+        self.send(
+            commands,
+            move |client, url| client.post(url).json(&score),
+            |response| LeaderboardResponse::Submitted(response.map(|_| ())),
+        );
+    }
+
+    fn health_check(&self, commands: &mut Commands) {
+        self.send(
+            commands,
+            |client, url| client.get(url),
+            |response| LeaderboardResponse::HealthChecked(response.map(|_| ())),
+        );
+    }
+}
+
+/// A leaderboard that never leaves this machine, for tests and offline play.
+///
+/// Scores are kept in memory, and also in a JSON file if it has a path.
+pub struct LocalLeaderboard {
+    scores: Mutex<Vec<HighScore>>,
+    path: Option<std::path::PathBuf>,
+}
+
+impl LocalLeaderboard {
+    pub const MAX_SCORES: usize = 100;
+
+    pub fn in_memory(scores: Vec<HighScore>) -> Self {
+        Self {
+            scores: Mutex::new(scores),
+            path: None,
+        }
+    }
+
+    /// Loads the leaderboard from `path`, starting out empty if there's nothing there yet.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn from_file(path: impl Into<std::path::PathBuf>) -> Self {
+        let path = path.into();
+
+        let scores = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!("couldn't read local leaderboard {}: {e}", path.display());
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Self {
+            path: Some(path),
+            ..Self::in_memory(scores)
+        }
+    }
+
+    fn scores(&self) -> std::sync::MutexGuard<'_, Vec<HighScore>> {
+        self.scores
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn save(&self, scores: &[HighScore]) -> Result<(), LeaderboardError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let json = serde_json::to_vec(scores).map_err(|e| LeaderboardError::Io(e.to_string()))?;
+        std::fs::write(path, json).map_err(|e| LeaderboardError::Io(e.to_string()))
+    }
+}
+
+impl LeaderboardClient for LocalLeaderboard {
+    fn fetch(&self, page: usize, commands: &mut Commands) {
+        let scores = self
+            .scores()
+            .iter()
+            .skip(page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+            .cloned()
+            .collect();
+
+        commands.trigger(LeaderboardResponse::Fetched {
+            page,
+            result: Ok(scores),
+        });
+    }
+
+    fn submit(&self, score: HighScore, commands: &mut Commands) {
+        let mut scores = self.scores();
+        scores.push(score);
+        scores.sort_by(|a, b| b.score.cmp(&a.score));
+        scores.truncate(Self::MAX_SCORES);
+
+        commands.trigger(LeaderboardResponse::Submitted(self.save(&scores)));
+    }
+
+    fn health_check(&self, commands: &mut Commands) {
+        commands.trigger(LeaderboardResponse::HealthChecked(Ok(())));
+    }
+}
+
+fn create_leaderboard_client(config: Res<LeaderboardConfig>, mut commands: Commands) {
+    let client: Arc<dyn LeaderboardClient> = match &config.url {
+        Some(url) => Arc::new(HttpLeaderboard { url: url.clone() }),
+        #[cfg(not(target_family = "wasm"))]
+        None => Arc::new(LocalLeaderboard::from_file("leaderboard.json")),
+        #[cfg(target_family = "wasm")]
+        None => Arc::new(LocalLeaderboard::in_memory(Vec::new())),
+    };
+
+    commands.insert_resource(Leaderboard(client));
+}
+
+/// Checks that the leaderboard is reachable and asks for the latest high scores.
+pub fn connect_to_leaderboard(mut requests: EventWriter<LeaderboardRequest>) {
+    requests.write_batch([
+        LeaderboardRequest::HealthCheck,
+        LeaderboardRequest::Fetch { page: 0 },
+    ]);
+}
+
+/// Asks for the latest top scores.
+pub fn request_high_scores(mut requests: EventWriter<LeaderboardRequest>) {
+    requests.write(LeaderboardRequest::Fetch { page: 0 });
+}
+
+fn handle_leaderboard_requests(
+    mut requests: EventReader<LeaderboardRequest>,
+    leaderboard: Option<Res<Leaderboard>>,
+    mut status: ResMut<LeaderboardStatus>,
+    mut commands: Commands,
+) {
+    // Requests stay around for a frame, so anything sent before the client exists isn't lost.
+    let Some(leaderboard) = leaderboard else {
+        return;
+    };
+
+    for request in requests.read() {
+        match request.clone() {
+            LeaderboardRequest::Fetch { page } => {
+                status.fetch = RequestState::Loading;
+                leaderboard.fetch(page, &mut commands);
+            }
+            LeaderboardRequest::Submit(score) => {
+                status.submit = RequestState::Loading;
+                leaderboard.submit(score, &mut commands);
+            }
+            LeaderboardRequest::HealthCheck => {
+                status.health = RequestState::Loading;
+                leaderboard.health_check(&mut commands);
+            }
+        }
+    }
+}
+
+fn apply_leaderboard_response(
+    trigger: Trigger<LeaderboardResponse>,
+    mut status: ResMut<LeaderboardStatus>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    let into_state = |result: Result<(), &LeaderboardError>| match result {
+        Ok(()) => RequestState::Success,
+        Err(e) => {
+            error!(%e);
+            RequestState::Failed(e.clone())
+        }
+    };
+
+    match trigger.event() {
+        LeaderboardResponse::Fetched { page, result } => {
+            if let Ok(scores) = result {
+                high_scores.apply_page(*page, scores.clone());
+            }
+            status.fetch = into_state(result.as_ref().map(|_| ()));
+        }
+        LeaderboardResponse::Submitted(result) => {
+            status.submit = into_state(result.as_ref().copied());
+        }
+        LeaderboardResponse::HealthChecked(result) => {
+            status.health = into_state(result.as_ref().copied());
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! Scores waiting to be submitted to the leaderboard.
+//!
+//! Submitting a score puts it in the [`Outbox`] first, which is saved to disk on native builds.
+//! Scores are then sent one at a time. If a submission fails, it's retried with exponential
+//! backoff, on this launch or a later one. Scores the leaderboard refuses are kept as
+//! [`OutboxState::Rejected`] and never sent again.
+
+use crate::leaderboard::{HighScore, LeaderboardError, LeaderboardRequest, LeaderboardResponse};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.insert_resource(Outbox::load());
+    app.add_observer(apply_submit_response);
+    app.add_observer(retry_when_reachable);
+
+    app.add_systems(Update, flush_outbox);
+}
+
+/// Where the outbox is saved between launches.
+#[cfg(not(target_family = "wasm"))]
+const OUTBOX_PATH: &str = "outbox.json";
+
+/// How long to wait before the first retry, doubled for every failed attempt.
+const BASE_RETRY_DELAY_SECS: f64 = 5.0;
+const MAX_RETRY_DELAY_SECS: f64 = 600.0;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxState {
+    Pending,
+    Submitted,
+    /// The leaderboard refused the score with this status code.
+    Rejected(u16),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct OutboxEntry {
+    /// The seed of the run the score is from.
+    pub seed: u64,
+    pub score: HighScore,
+    pub state: OutboxState,
+    /// How many submissions have failed so far.
+    pub attempts: u32,
+    /// When to try again, in seconds of [`Time<Real>`]. Every pending score is retried at launch.
+    #[serde(skip)]
+    pub retry_at: f64,
+}
+
+impl OutboxEntry {
+    /// A run has one score, no matter which name it's submitted under. Playing a replay back
+    /// reproduces both the seed and the score, so it won't be submitted again either.
+    fn is_same_run(&self, seed: u64, score: u128) -> bool {
+        self.seed == seed && self.score.score == score
+    }
+}
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Outbox {
+    pub entries: Vec<OutboxEntry>,
+    /// Index of the entry that is being submitted.
+    in_flight: Option<usize>,
+    /// Whether the last submission couldn't reach the leaderboard.
+    unreachable: bool,
+}
+
+impl Outbox {
+    #[cfg(not(target_family = "wasm"))]
+    fn load() -> Self {
+        let entries = match std::fs::read(OUTBOX_PATH) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!("couldn't read the score outbox {OUTBOX_PATH}: {e}");
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+
+        Self {
+            entries,
+            ..default()
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    fn load() -> Self {
+        Self::default()
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    fn save(&self) {
+        let result = serde_json::to_vec(&self.entries)
+            .map_err(std::io::Error::from)
+            .and_then(|json| std::fs::write(OUTBOX_PATH, json));
+
+        if let Err(e) = result {
+            error!(?e);
+        }
+    }
+
+    #[cfg(target_family = "wasm")]
+    #[expect(clippy::unused_self)]
+    const fn save(&self) {}
+
+    /// The entry for the score of the run with this seed, if it was submitted.
+    pub fn entry(&self, seed: u64, score: u128) -> Option<&OutboxEntry> {
+        self.entries.iter().find(|it| it.is_same_run(seed, score))
+    }
+
+    /// Queues a score for submission. Returns `false` if the run's score was already queued.
+    pub fn push(&mut self, seed: u64, score: HighScore) -> bool {
+        if self.entry(seed, score.score).is_some() {
+            return false;
+        }
+
+        self.entries.push(OutboxEntry {
+            seed,
+            score,
+            state: OutboxState::Pending,
+            attempts: 0,
+            retry_at: 0.0,
+        });
+        self.save();
+
+        true
+    }
+
+    pub fn pending(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|it| it.state == OutboxState::Pending)
+            .count()
+    }
+}
+
+fn flush_outbox(
+    mut outbox: ResMut<Outbox>,
+    time: Res<Time<Real>>,
+    mut requests: EventWriter<LeaderboardRequest>,
+) {
+    if outbox.in_flight.is_some() {
+        return;
+    }
+
+    let now = time.elapsed_secs_f64();
+    let Some(idx) = outbox
+        .entries
+        .iter()
+        .position(|it| it.state == OutboxState::Pending && it.retry_at <= now)
+    else {
+        return;
+    };
+
+    outbox.in_flight = Some(idx);
+    requests.write(LeaderboardRequest::Submit(
+        outbox.entries[idx].score.clone(),
+    ));
+}
+
+fn apply_submit_response(
+    trigger: Trigger<LeaderboardResponse>,
+    mut outbox: ResMut<Outbox>,
+    time: Res<Time<Real>>,
+    mut requests: EventWriter<LeaderboardRequest>,
+) {
+    let LeaderboardResponse::Submitted(result) = trigger.event() else {
+        return;
+    };
+    let Some(idx) = outbox.in_flight.take() else {
+        return;
+    };
+
+    outbox.unreachable = matches!(result, Err(LeaderboardError::Network(_)));
+
+    let Some(entry) = outbox.entries.get_mut(idx) else {
+        return;
+    };
+
+    match result {
+        Ok(()) => {
+            entry.state = OutboxState::Submitted;
+            // Show the new score on the leaderboard.
+            requests.write(LeaderboardRequest::Fetch { page: 0 });
+        }
+        Err(LeaderboardError::Status(code)) if is_rejection(*code) => {
+            warn!(
+                "the leaderboard rejected {:?} with status {code}",
+                entry.score
+            );
+            entry.state = OutboxState::Rejected(*code);
+        }
+        Err(_) => {
+            let delay = (BASE_RETRY_DELAY_SECS * f64::from(1u32 << entry.attempts.min(16)))
+                .min(MAX_RETRY_DELAY_SECS);
+            entry.attempts += 1;
+            entry.retry_at = time.elapsed_secs_f64() + delay;
+            info!("retrying the submission of {:?} in {delay}s", entry.score);
+        }
+    }
+
+    outbox.save();
+}
+
+/// Client errors mean the score itself is the problem, and sending it again won't help, except for
+/// timeouts and rate limiting.
+const fn is_rejection(code: u16) -> bool {
+    matches!(code, 400..=499) && !matches!(code, 408 | 429)
+}
+
+/// Once the leaderboard is reachable again, there's no reason to keep waiting.
+fn retry_when_reachable(trigger: Trigger<LeaderboardResponse>, mut outbox: ResMut<Outbox>) {
+    let reachable = match trigger.event() {
+        LeaderboardResponse::HealthChecked(result) => result.is_ok(),
+        // Fetching works even while submissions fail, so only count it after a network error.
+        LeaderboardResponse::Fetched { result, .. } => result.is_ok() && outbox.unreachable,
+        LeaderboardResponse::Submitted(_) => false,
+    };
+
+    if !reachable {
+        return;
+    }
+
+    outbox.unreachable = false;
+    for entry in &mut outbox.entries {
+        entry.retry_at = 0.0;
+    }
+}
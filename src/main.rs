@@ -6,55 +6,98 @@ extern crate core;
 
 mod asset_tracking;
 mod audio;
+#[cfg(not(target_family = "wasm"))]
+mod cli;
 // mod demo;
 pub mod assets;
 #[cfg(feature = "dev")]
 mod dev_tools;
+mod leaderboard;
 mod menus;
 mod parrylord;
 mod screens;
+mod telemetry;
 mod theme;
 mod zaphkiel;
 
-use crate::assets::{GameplayMusic, MusicAudio, NotGameplayMusic};
-use crate::audio::music;
 use avian2d::prelude::Gravity;
 use avian2d::PhysicsPlugins;
-use bevy::time::common_conditions::on_timer;
-use bevy::window::WindowResolution;
+use bevy::app::ScheduleRunnerPlugin;
+use bevy::render::settings::WgpuSettings;
+use bevy::render::RenderPlugin;
+use bevy::window::{ExitCondition, WindowResolution};
+use bevy::winit::WinitPlugin;
 use bevy::{asset::AssetMetaCheck, prelude::*};
-use bevy_mod_reqwest::{BevyReqwest, JsonResponse, ReqwestErrorEvent, ReqwestPlugin};
+use bevy_mod_reqwest::ReqwestPlugin;
 use std::time::Duration;
 
+#[cfg(not(target_family = "wasm"))]
 fn main() -> AppExit {
-    App::new().add_plugins(AppPlugin).run()
+    let cli = <cli::Cli as clap::Parser>::parse();
+
+    App::new()
+        .add_plugins(AppPlugin {
+            headless: cli.headless(),
+        })
+        .add_plugins(cli)
+        .run()
 }
 
-pub struct AppPlugin;
+#[cfg(target_family = "wasm")]
+fn main() -> AppExit {
+    App::new().add_plugins(AppPlugin::default()).run()
+}
+
+#[derive(Default)]
+pub struct AppPlugin {
+    /// Run without a window or renderer.
+    pub headless: bool,
+}
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
         // Add Bevy plugins.
-        app.add_plugins(
-            DefaultPlugins
-                .set(AssetPlugin {
-                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
-                    // This causes errors and even panics on web build on itch.
-                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
-                    meta_check: AssetMetaCheck::Never,
+        let default_plugins = DefaultPlugins
+            .set(AssetPlugin {
+                // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                // This causes errors and even panics on web build on itch.
+                // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            .set(WindowPlugin {
+                primary_window: Window {
+                    title: "Parrylord".to_string(),
+                    fit_canvas_to_parent: true,
+                    resolution: WindowResolution::new(1920.0, 1080.0),
                     ..default()
-                })
-                .set(WindowPlugin {
-                    primary_window: Window {
-                        title: "Parrylord".to_string(),
-                        fit_canvas_to_parent: true,
-                        resolution: WindowResolution::new(1920.0, 1080.0),
+                }
+                .into(),
+                ..default()
+            });
+
+        if self.headless {
+            app.add_plugins((
+                default_plugins
+                    .set(WindowPlugin {
+                        primary_window: None,
+                        exit_condition: ExitCondition::DontExit,
                         ..default()
-                    }
-                    .into(),
-                    ..default()
-                }),
-        );
+                    })
+                    .set(RenderPlugin {
+                        render_creation: WgpuSettings {
+                            backends: None,
+                            ..default()
+                        }
+                        .into(),
+                        ..default()
+                    })
+                    .disable::<WinitPlugin>(),
+                ScheduleRunnerPlugin::run_loop(Duration::ZERO),
+            ));
+        } else {
+            app.add_plugins(default_plugins);
+        }
 
         // Add other plugins.
         app.add_plugins((
@@ -64,9 +107,12 @@ impl Plugin for AppPlugin {
             parrylord::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
+            leaderboard::plugin,
             menus::plugin,
             screens::plugin,
+            telemetry::plugin,
             theme::plugin,
+            zaphkiel::plugin,
             ReqwestPlugin::default(),
         ));
 
@@ -92,12 +138,6 @@ impl Plugin for AppPlugin {
         app.add_systems(Startup, spawn_camera);
 
         app.init_resource::<ParrylordSingleton>();
-        app.init_resource::<HighScores>();
-
-        app.add_systems(
-            Update,
-            get_high_scores.run_if(on_timer(Duration::from_secs_f32(10.0))),
-        );
 
         app.init_resource::<AudioSpawned>();
     }
@@ -140,12 +180,16 @@ fn spawn_camera(mut commands: Commands) {
     ));
 }
 
-#[derive(Resource, Clone, Reflect, Debug)]
+#[derive(Resource, Clone, Reflect, Debug, serde::Serialize)]
 #[reflect(Resource)]
 pub struct ParrylordSingleton {
     pub enemies_killed: u32,
     pub level: u32,
     pub max_parried: u32,
+    /// How many player attacks cancelled a whole volley of enemy attacks.
+    pub volleys_cleared: u32,
+    /// The most times an attack went back and forth between the player and elite enemies.
+    pub longest_rally: u32,
 }
 
 impl Default for ParrylordSingleton {
@@ -154,6 +198,8 @@ impl Default for ParrylordSingleton {
             enemies_killed: 0,
             level: 1,
             max_parried: 0,
+            volleys_cleared: 0,
+            longest_rally: 0,
         }
     }
 }
@@ -165,44 +211,18 @@ impl ParrylordSingleton {
             enemies_killed,
             level,
             max_parried,
+            volleys_cleared,
+            longest_rally,
         } = self;
 
         let enemies_killed = enemies_killed as u128;
         let level = level as u128;
-        let max_parried = max_parried;
+        let volleys_cleared = volleys_cleared as u128;
+        let longest_rally = longest_rally as u128;
 
-        (level + enemies_killed).saturating_pow(max_parried)
+        (level + enemies_killed + volleys_cleared + longest_rally).saturating_pow(max_parried)
     }
 }
 
-#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Default)]
-pub struct HighScore {
-    pub name: String,
-    pub score: u128,
-}
-
-#[derive(serde::Deserialize, Debug, Clone, Default, Resource)]
-pub struct HighScores(pub Vec<HighScore>);
-
-pub const CF_WORKER_URL: &str = "https://parrylord-high-score-worker.pawarherschel.workers.dev/";
-
-fn get_high_scores(mut client: BevyReqwest) {
-    let reqwest_request = client.get(CF_WORKER_URL).build().unwrap();
-
-    client
-        .send(reqwest_request)
-        .on_json_response(
-            |trigger: Trigger<JsonResponse<HighScores>>, mut high_scores: ResMut<HighScores>| {
-                let data = trigger.0.clone();
-
-                high_scores.0 = data.0;
-            },
-        )
-        .on_error(|trigger: Trigger<ReqwestErrorEvent>| {
-            let e = &trigger.event().0;
-            error!(?e);
-        });
-}
-
 #[derive(Resource, Default)]
 pub struct AudioSpawned(pub(crate) bool);
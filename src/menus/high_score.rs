@@ -1,16 +1,26 @@
 use crate::asset_tracking::ResourceHandles;
+use crate::leaderboard::history::{format_date, RunHistory, RunRecord};
+use crate::leaderboard::outbox::{Outbox, OutboxState};
+use crate::leaderboard::{
+    HighScore, HighScores, LeaderboardRequest, LeaderboardStatus, RequestState, RunMetadata,
+};
 use crate::menus::Menu;
+use crate::parrylord::level::RunDuration;
+use crate::parrylord::mode::GameMode;
+use crate::parrylord::replay::ReplayRecorder;
+use crate::parrylord::rng::RunSeed;
 use crate::screens::Screen;
-use crate::theme::palette::LABEL_TEXT;
+use crate::theme::palette::HIGHLIGHTED_ROW_BACKGROUND;
+use crate::theme::text_input::{TextInput, TextInputSubmit};
 use crate::theme::widget;
-use crate::zaphkiel::has_bad_word;
-use crate::{HighScore, HighScores, ParrylordSingleton, CF_WORKER_URL};
+use crate::zaphkiel::profanity::ProfanityFilter;
+use crate::ParrylordSingleton;
+use bevy::ecs::spawn::SpawnWith;
+use bevy::input_focus::InputFocus;
 use bevy::prelude::*;
-use bevy_mod_reqwest::{BevyReqwest, ReqwestErrorEvent};
-use std::cmp::PartialEq;
 
 pub fn plugin(app: &mut App) {
-    app.init_resource::<NameField>();
+    app.init_resource::<HighScoreTab>();
     app.add_systems(OnEnter(Menu::HighScore), spawn_high_score);
     // app.add_systems(
     //     Update,
@@ -20,23 +30,39 @@ pub fn plugin(app: &mut App) {
     // );
     app.add_systems(
         Update,
-        (update_name, tick_inactive_timer).run_if(in_state(Menu::HighScore)),
+        (
+            tick_inactive_timer,
+            update_high_score_list.run_if(
+                resource_changed::<HighScores>
+                    .or(resource_changed::<RunHistory>)
+                    .or(resource_changed::<HighScoreTab>)
+                    .or(resource_changed::<LeaderboardStatus>)
+                    .or(resource_changed::<Outbox>)
+                    .or(any_match_filter::<Added<HighScoreList>>),
+            ),
+            load_more_scores,
+            update_leaderboard_status.run_if(resource_changed::<Outbox>),
+        )
+            .run_if(in_state(Menu::HighScore)),
     );
 }
 
 fn spawn_high_score(
     mut commands: Commands,
     singleton: Res<ParrylordSingleton>,
-    scores: Res<HighScores>,
+    history: Res<RunHistory>,
+    mode: Res<GameMode>,
 ) {
     let ParrylordSingleton {
         enemies_killed,
         level,
         max_parried,
+        volleys_cleared,
+        longest_rally,
     } = *singleton;
     let score = singleton.calculate_score();
 
-    let scores = scores.0.clone();
+    let personal_best = history.personal_best(*mode).map_or(0, |it| it.score);
 
     let root = commands.spawn(widget::ui_root("High Score")).id();
 
@@ -50,36 +76,347 @@ fn spawn_high_score(
         StateScoped(Menu::HighScore),
         children![
             (
-                widget::label("Name: "),
-                children![(
-                    TextSpan::new("<Start Typing>"),
-                    TextThing,
-                    TextFont::from_font_size(24.0),
-                    TextColor(LABEL_TEXT),
-                )]
+                widget::row(),
+                Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+                    parent.spawn(widget::label("Name:"));
+                    parent
+                        .spawn((
+                            widget::text_input(NAME_PLACEHOLDER, MAX_NAME_LEN),
+                            NameInput,
+                        ))
+                        .observe(submit_score::<TextInputSubmit>);
+                })),
             ),
             widget::header(format!("Score: {score}")),
             widget::label(format!("Enemies Killed: {enemies_killed}")),
             widget::label(format!("Level Reached: {level}")),
             widget::label(format!("Max Projectiles Parried: {max_parried}")),
-            widget::button("Submit Score", submit_score),
-            widget::button("Play Again", enter_loading_or_gameplay_screen),
+            widget::label(format!("Volleys Cleared: {volleys_cleared}")),
+            widget::label(format!("Longest Rally: {longest_rally}")),
+            widget::label(format!("Personal Best ({mode:?}): {personal_best}")),
         ],
     ));
 
     root.with_children(|children_spawner| {
-        for (pos, HighScore { name, score }) in
-            (0..10).map(|idx| (idx + 1, scores.get(idx).cloned().unwrap_or_default()))
-        {
-            children_spawner.spawn(widget::label(format!("{pos}: {name} -> {score}")));
+        if history.new_personal_best {
+            children_spawner.spawn(widget::header("New personal best!"));
         }
 
-        children_spawner.spawn(widget::button("Main Menu", open_main_menu));
+        children_spawner.spawn((
+            widget::row(),
+            children![
+                widget::button("Submit Score", submit_score::<Pointer<Click>>),
+                widget::button("Play Again", enter_loading_or_gameplay_screen),
+                widget::button("Main Menu", open_main_menu),
+            ],
+        ));
+        children_spawner.spawn((widget::label(""), LeaderboardStatusLabel));
+
+        children_spawner.spawn((
+            widget::row(),
+            children![
+                widget::button("Global", show_global_scores),
+                widget::button("Local", show_local_scores),
+            ],
+        ));
+        children_spawner.spawn((
+            widget::scroll_list("High Score List", Val::Px(8.0 * ROW_HEIGHT)),
+            HighScoreList::default(),
+        ));
     });
 
     commands.insert_resource(Inactive(Timer::from_seconds(1.0, TimerMode::Once)));
 }
 
+/// Which leaderboard the high score list shows.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum HighScoreTab {
+    #[default]
+    Global,
+    /// Runs played on this machine.
+    Local,
+}
+
+fn show_global_scores(_: Trigger<Pointer<Click>>, mut tab: ResMut<HighScoreTab>) {
+    *tab = HighScoreTab::Global;
+}
+
+fn show_local_scores(_: Trigger<Pointer<Click>>, mut tab: ResMut<HighScoreTab>) {
+    *tab = HighScoreTab::Local;
+}
+
+/// Height of a row in the [`HighScoreList`], in logical pixels.
+const ROW_HEIGHT: f32 = 36.0;
+
+/// The scrollable list of scores of the current [`HighScoreTab`].
+#[derive(Component, Debug, Default)]
+struct HighScoreList {
+    /// The row of the player's own score, if it's in the list.
+    highlighted: Option<usize>,
+}
+
+/// A row of the [`HighScoreList`]: rank, name, score and date.
+struct HighScoreRow {
+    name: String,
+    score: u128,
+    date: Option<u64>,
+}
+
+impl HighScoreRow {
+    fn global(score: &HighScore) -> Self {
+        let name = match &score.run {
+            Some(run) => format!("{} ({:?}, level {})", score.name, run.mode, run.level),
+            None => score.name.clone(),
+        };
+
+        Self {
+            name,
+            score: score.score,
+            date: score.date,
+        }
+    }
+
+    fn local(run: &RunRecord) -> Self {
+        Self {
+            name: format!("{:?}, level {}, seed {}", run.mode, run.level, run.seed),
+            score: run.score,
+            date: run.date,
+        }
+    }
+
+    fn bundle(&self, rank: usize, highlighted: bool) -> impl Bundle {
+        let date = self.date.map_or_else(|| String::from("-"), format_date);
+
+        (
+            Name::new("High Score Row"),
+            Node {
+                height: Val::Px(ROW_HEIGHT),
+                flex_shrink: 0.0,
+                column_gap: Val::Px(20.0),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(if highlighted {
+                HIGHLIGHTED_ROW_BACKGROUND
+            } else {
+                Color::NONE
+            }),
+            // Let the list underneath see the mouse wheel.
+            Pickable::IGNORE,
+            children![
+                list_cell(format!("{rank}."), 60.0),
+                list_cell(self.name.clone(), 480.0),
+                list_cell(self.score.to_string(), 240.0),
+                list_cell(date, 160.0),
+            ],
+        )
+    }
+}
+
+fn list_cell(text: String, width: f32) -> impl Bundle {
+    (
+        widget::label(text),
+        Node {
+            width: Val::Px(width),
+            ..default()
+        },
+        Pickable::IGNORE,
+    )
+}
+
+/// The last row of the [`HighScoreList`], saying whether there's more to see.
+fn list_footer(text: impl Into<String>) -> impl Bundle {
+    (
+        widget::label(text),
+        Node {
+            height: Val::Px(ROW_HEIGHT),
+            flex_shrink: 0.0,
+            ..default()
+        },
+        Pickable::IGNORE,
+    )
+}
+
+/// The global leaderboard entry of the current run's score, once it's been submitted.
+fn own_score<'a>(
+    outbox: &'a Outbox,
+    singleton: &ParrylordSingleton,
+    seed: &RunSeed,
+) -> Option<&'a HighScore> {
+    outbox
+        .entry(seed.0, singleton.calculate_score())
+        .filter(|it| it.state == OutboxState::Submitted)
+        .map(|it| &it.score)
+}
+
+fn is_own_score(own: &HighScore, score: &HighScore) -> bool {
+    own.name == score.name
+        && own.score == score.score
+        && own
+            .run
+            .as_ref()
+            .zip(score.run.as_ref())
+            .is_none_or(|(own, other)| own.seed == other.seed)
+}
+
+fn update_high_score_list(
+    scores: Res<HighScores>,
+    history: Res<RunHistory>,
+    tab: Res<HighScoreTab>,
+    status: Res<LeaderboardStatus>,
+    outbox: Res<Outbox>,
+    singleton: Res<ParrylordSingleton>,
+    seed: Res<RunSeed>,
+    list: Single<(Entity, &mut HighScoreList, &mut ScrollPosition)>,
+    mut commands: Commands,
+) {
+    let (entity, mut list, mut scroll_position) = list.into_inner();
+
+    let (rows, highlighted, footer) = match *tab {
+        HighScoreTab::Global => {
+            let own = own_score(&outbox, &singleton, &seed);
+            let highlighted = own.and_then(|own| {
+                scores
+                    .scores
+                    .iter()
+                    .position(|score| is_own_score(own, score))
+            });
+
+            let footer = match (&status.fetch, scores.complete) {
+                (RequestState::Loading, _) => String::from("Loading..."),
+                (RequestState::Failed(e), _) => format!("Couldn't load scores: {e}"),
+                (_, true) if scores.scores.is_empty() => String::from("No scores yet"),
+                (_, true) => String::from("That's everyone!"),
+                (_, false) => String::from("Scroll for more"),
+            };
+
+            let rows = scores
+                .scores
+                .iter()
+                .map(HighScoreRow::global)
+                .collect::<Vec<_>>();
+
+            (rows, highlighted, footer)
+        }
+        HighScoreTab::Local => {
+            let top_runs = history.top_runs();
+            let latest = history.runs.last();
+            let highlighted = latest
+                .and_then(|latest| top_runs.iter().position(|run| std::ptr::eq(*run, latest)));
+
+            let footer = if top_runs.is_empty() {
+                String::from("No runs yet")
+            } else {
+                format!("Your last {} runs", top_runs.len())
+            };
+
+            let rows = top_runs
+                .into_iter()
+                .map(HighScoreRow::local)
+                .collect::<Vec<_>>();
+
+            (rows, highlighted, footer)
+        }
+    };
+
+    commands.entity(entity).despawn_related::<Children>();
+    commands.entity(entity).with_children(|children_spawner| {
+        for (idx, row) in rows.iter().enumerate() {
+            children_spawner.spawn(row.bundle(idx + 1, highlighted == Some(idx)));
+        }
+
+        children_spawner.spawn(list_footer(footer));
+    });
+
+    // Jump to the player's score when it first shows up.
+    if highlighted.is_some() && highlighted != list.highlighted {
+        let idx = highlighted.unwrap_or_default() as f32;
+        scroll_position.offset_y = ((idx - 3.0) * ROW_HEIGHT).max(0.0);
+    }
+    list.highlighted = highlighted;
+}
+
+/// Fetches the next page of the global leaderboard when the list is scrolled to the bottom, or
+/// while looking for the player's own score.
+fn load_more_scores(
+    scores: Res<HighScores>,
+    tab: Res<HighScoreTab>,
+    status: Res<LeaderboardStatus>,
+    outbox: Res<Outbox>,
+    singleton: Res<ParrylordSingleton>,
+    seed: Res<RunSeed>,
+    list: Single<(&HighScoreList, &ScrollPosition, &ComputedNode)>,
+    mut requests: EventWriter<LeaderboardRequest>,
+) {
+    let (list, scroll_position, computed_node) = list.into_inner();
+
+    if *tab != HighScoreTab::Global
+        || matches!(
+            status.fetch,
+            RequestState::Loading | RequestState::Failed(_)
+        )
+    {
+        return;
+    }
+    let Some(page) = scores.next_page() else {
+        return;
+    };
+
+    let visible_bottom =
+        scroll_position.offset_y + computed_node.size().y * computed_node.inverse_scale_factor();
+    let content_height = computed_node.content_size().y * computed_node.inverse_scale_factor();
+    let at_bottom = visible_bottom >= content_height - 2.0 * ROW_HEIGHT;
+
+    // Scores are sorted, so the player's own is only further down if it's lower than the last one.
+    let own_further_down = list.highlighted.is_none()
+        && own_score(&outbox, &singleton, &seed).is_some_and(|own| {
+            scores
+                .scores
+                .last()
+                .is_some_and(|last| own.score <= last.score)
+        });
+
+    if at_bottom || own_further_down {
+        requests.write(LeaderboardRequest::Fetch { page });
+    }
+}
+
+/// Shows what the leaderboard is up to.
+#[derive(Component)]
+struct LeaderboardStatusLabel;
+
+fn update_leaderboard_status(
+    outbox: Res<Outbox>,
+    singleton: Res<ParrylordSingleton>,
+    seed: Res<RunSeed>,
+    mut label: Single<&mut Text, With<LeaderboardStatusLabel>>,
+) {
+    let entry = outbox.entry(seed.0, singleton.calculate_score());
+
+    let mut text = match entry.map(|it| (it.state, it.attempts)) {
+        Some((OutboxState::Pending, 0)) => String::from("Submitting..."),
+        Some((OutboxState::Pending, attempts)) => {
+            format!("Couldn't submit score, retrying (attempt {})", attempts + 1)
+        }
+        Some((OutboxState::Submitted, _)) => String::from("Score submitted!"),
+        Some((OutboxState::Rejected(code), _)) => {
+            format!("The leaderboard rejected this score (status {code})")
+        }
+        None => String::new(),
+    };
+
+    // Scores from earlier runs that are still waiting.
+    let waiting =
+        outbox.pending() - usize::from(entry.is_some_and(|it| it.state == OutboxState::Pending));
+    if waiting > 0 {
+        text.push_str(&format!(
+            "\n{waiting} earlier score(s) waiting to be submitted"
+        ));
+    }
+
+    label.0 = text;
+}
+
 fn enter_loading_or_gameplay_screen(
     _: Trigger<Pointer<Click>>,
     resource_handles: Res<ResourceHandles>,
@@ -109,24 +446,23 @@ fn open_main_menu(
     next_menu.set(Menu::Main);
 }
 
-#[derive(Component)]
-struct TextThing;
-
-#[derive(Resource, Debug, Eq, PartialEq)]
-struct NameField(String);
+const NAME_PLACEHOLDER: &str = "Type your name";
+const MAX_NAME_LEN: usize = 24;
 
-impl Default for NameField {
-    fn default() -> Self {
-        Self(String::from("<Start Typing>"))
-    }
-}
+/// The text field for the name to submit the score under.
+#[derive(Component)]
+struct NameInput;
 
 #[derive(Resource)]
 struct Inactive(Timer);
 
+/// Also focuses the name field once the menu becomes active, so keys still held from the last
+/// moments of the run don't end up in it.
 fn tick_inactive_timer(
-    mut timer: Option<ResMut<Inactive>>,
+    timer: Option<ResMut<Inactive>>,
     time: Res<Time>,
+    name_input: Single<Entity, With<NameInput>>,
+    mut focus: ResMut<InputFocus>,
     mut commands: Commands,
 ) {
     let Some(mut timer) = timer else {
@@ -136,130 +472,62 @@ fn tick_inactive_timer(
     timer.0.tick(time.delta());
     if timer.0.just_finished() {
         commands.remove_resource::<Inactive>();
+        focus.set(*name_input);
     }
 }
 
-fn submit_score(
-    _: Trigger<Pointer<Click>>,
+/// Submits on the button, or on Enter in the name field.
+fn submit_score<E: Event>(
+    _: Trigger<E>,
     singleton: Res<ParrylordSingleton>,
-    mut name_field: ResMut<NameField>,
-    mut next_menu: ResMut<NextState<Menu>>,
-    mut client: BevyReqwest,
+    mut name_input: Single<&mut TextInput, With<NameInput>>,
+    profanity_filter: Option<Res<ProfanityFilter>>,
+    seed: Res<RunSeed>,
+    mode: Res<GameMode>,
+    run_duration: Res<RunDuration>,
+    recorder: Res<ReplayRecorder>,
+    mut outbox: ResMut<Outbox>,
     inactive: Option<Res<Inactive>>,
 ) {
     if inactive.is_some() {
         return;
     }
 
-    if has_bad_word(&name_field.0) {
-        name_field.0 = String::from("BAD WORD DETECTED");
-        return;
-    }
+    let name = name_input.value().trim().to_owned();
 
-    let score = singleton.calculate_score();
+    // The word lists are loaded with the rest of the assets, before gameplay can start, so this
+    // only happens if they failed to load, which isn't the name's fault.
+    let Some(profanity_filter) = profanity_filter else {
+        warn!("can't check names without the profanity filter");
+        name_input.clear();
+        name_input.placeholder = String::from("Can't check names right now, try again");
+        return;
+    };
 
-    if *name_field == NameField::default()
-        || score == 1
-        || name_field
-            .0
-            .as_str()
-            .eq_ignore_ascii_case("BAD WORD DETECTED")
-    {
+    if profanity_filter.is_profane(&name) {
+        name_input.clear();
+        name_input.placeholder = String::from("That name isn't allowed");
         return;
     }
 
-    let reqwest_request = client
-        .post(CF_WORKER_URL)
-        .json(&HighScore {
-            name: name_field.0.clone(),
-            score,
-        })
-        .build()
-        .unwrap();
-
-    client
-        .send(reqwest_request)
-        .on_error(|trigger: Trigger<ReqwestErrorEvent>| {
-            let e = &trigger.event().0;
-            error!(?e);
-        });
-
-    next_menu.set(Menu::Main);
-}
+    let score = singleton.calculate_score();
 
-fn update_name(
-    key: Res<ButtonInput<KeyCode>>,
-    mut name_field: ResMut<NameField>,
-    mut text_thing: Single<&mut TextSpan, With<TextThing>>,
-    inactive: Option<Res<Inactive>>,
-) {
-    if inactive.is_some() {
+    if name.is_empty() || score == 1 {
         return;
     }
 
-    let keys_pressed = key.get_just_pressed().collect::<Vec<_>>();
+    let replay = recorder.0.compress().inspect_err(|e| error!(?e)).ok();
 
-    if (*name_field == NameField::default()
-        || name_field
-            .0
-            .as_str()
-            .eq_ignore_ascii_case("BAD WORD DETECTED"))
-        && !keys_pressed.is_empty()
-    {
-        name_field.0 = String::new();
-    }
-
-    for &key in keys_pressed {
-        if key == KeyCode::Backspace {
-            let new_len = name_field.0.len().saturating_sub(1);
-            name_field.0.truncate(new_len);
-        } else {
-            name_field.0.push(match key {
-                KeyCode::Digit0 => '0',
-                KeyCode::Digit1 => '1',
-                KeyCode::Digit2 => '2',
-                KeyCode::Digit3 => '3',
-                KeyCode::Digit4 => '4',
-                KeyCode::Digit5 => '5',
-                KeyCode::Digit6 => '6',
-                KeyCode::Digit7 => '7',
-                KeyCode::Digit8 => '8',
-                KeyCode::Digit9 => '9',
-                KeyCode::KeyA => 'A',
-                KeyCode::KeyB => 'B',
-                KeyCode::KeyC => 'C',
-                KeyCode::KeyD => 'D',
-                KeyCode::KeyE => 'E',
-                KeyCode::KeyF => 'F',
-                KeyCode::KeyG => 'G',
-                KeyCode::KeyH => 'H',
-                KeyCode::KeyI => 'I',
-                KeyCode::KeyJ => 'J',
-                KeyCode::KeyK => 'K',
-                KeyCode::KeyL => 'L',
-                KeyCode::KeyM => 'M',
-                KeyCode::KeyN => 'N',
-                KeyCode::KeyO => 'O',
-                KeyCode::KeyP => 'P',
-                KeyCode::KeyQ => 'Q',
-                KeyCode::KeyR => 'R',
-                KeyCode::KeyS => 'S',
-                KeyCode::KeyT => 'T',
-                KeyCode::KeyU => 'U',
-                KeyCode::KeyV => 'V',
-                KeyCode::KeyW => 'W',
-                KeyCode::KeyX => 'X',
-                KeyCode::KeyY => 'Y',
-                KeyCode::KeyZ => 'Z',
-                KeyCode::Space => ' ',
-                _ => continue,
-            });
-        }
-    }
+    let queued = outbox.push(
+        seed.0,
+        HighScore::new(
+            name,
+            RunMetadata::new(&singleton, *mode, seed.0, run_duration.0),
+            replay,
+        ),
+    );
 
-    if name_field.0.is_empty() {
-        *name_field = NameField::default();
+    if !queued {
+        info!("not submitting score {score} again");
     }
-
-    (***text_thing).clone_from(&name_field.0);
 }
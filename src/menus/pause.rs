@@ -2,7 +2,12 @@
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
-use crate::{menus::Menu, screens::Screen, theme::widget};
+use crate::{
+    menus::Menu,
+    screens::Screen,
+    theme::{palette::HEADER_TEXT, widget},
+    zaphkiel::tween::{Repeat, Tween, TweenTarget},
+};
 
 pub fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Pause), spawn_pause_menu);
@@ -18,7 +23,21 @@ fn spawn_pause_menu(mut commands: Commands) {
         GlobalZIndex(2),
         StateScoped(Menu::Pause),
         children![
-            widget::header("Game paused"),
+            (
+                widget::header("Game paused"),
+                // Game time is stopped, so this runs on real time.
+                Tween::new(
+                    TweenTarget::TextColor {
+                        start: HEADER_TEXT,
+                        end: HEADER_TEXT.with_alpha(0.5),
+                    },
+                    0.8,
+                )
+                .with_ease(EaseFunction::SineInOut)
+                .with_repeat(Repeat::Forever)
+                .with_yoyo()
+                .with_real_time(),
+            ),
             widget::button("Continue", close_menu),
             widget::button("Settings", open_settings_menu),
             widget::button("Quit to title", quit_to_title),
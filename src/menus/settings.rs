@@ -2,8 +2,12 @@
 //!
 //! Additional settings and accessibility options should go here.
 
-use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*};
+use bevy::{
+    audio::Volume, ecs::spawn::SpawnWith, input::common_conditions::input_just_pressed, prelude::*,
+};
 
+#[cfg(not(target_family = "wasm"))]
+use crate::telemetry::TelemetrySettings;
 use crate::{menus::Menu, screens::Screen, theme::prelude::*};
 
 pub fn plugin(app: &mut App) {
@@ -18,6 +22,15 @@ pub fn plugin(app: &mut App) {
         Update,
         update_global_volume_label.run_if(in_state(Menu::Settings)),
     );
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        app.register_type::<TelemetryLabel>();
+        app.add_systems(
+            Update,
+            update_telemetry_label.run_if(in_state(Menu::Settings)),
+        );
+    }
 }
 
 fn spawn_settings_menu(mut commands: Commands) {
@@ -43,16 +56,28 @@ fn settings_grid() -> impl Bundle {
             grid_template_columns: RepeatedGridTrack::px(2, 400.0),
             ..default()
         },
-        children![
-            (
+        Children::spawn(SpawnWith(|parent: &mut ChildSpawner| {
+            parent.spawn((
                 widget::label("Master Volume"),
                 Node {
                     justify_self: JustifySelf::End,
                     ..default()
-                }
-            ),
-            global_volume_widget(),
-        ],
+                },
+            ));
+            parent.spawn(global_volume_widget());
+
+            #[cfg(not(target_family = "wasm"))]
+            {
+                parent.spawn((
+                    widget::label("Run Telemetry"),
+                    Node {
+                        justify_self: JustifySelf::End,
+                        ..default()
+                    },
+                ));
+                parent.spawn(telemetry_widget());
+            }
+        })),
     )
 }
 
@@ -104,6 +129,47 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+#[cfg(not(target_family = "wasm"))]
+fn telemetry_widget() -> impl Bundle {
+    (
+        Name::new("Telemetry Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("~", toggle_telemetry),
+            (
+                Name::new("Current Telemetry"),
+                Node {
+                    padding: UiRect::horizontal(Val::Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), TelemetryLabel)],
+            ),
+        ],
+    )
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn toggle_telemetry(_: Trigger<Pointer<Click>>, mut settings: ResMut<TelemetrySettings>) {
+    settings.enabled = !settings.enabled;
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct TelemetryLabel;
+
+#[cfg(not(target_family = "wasm"))]
+fn update_telemetry_label(
+    settings: Res<TelemetrySettings>,
+    mut label: Single<&mut Text, With<TelemetryLabel>>,
+) {
+    label.0 = String::from(if settings.enabled { "On" } else { "Off" });
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -0,0 +1,270 @@
+//! Utility AI for enemies, loaded from `.ai.json` files.
+//!
+//! Whenever an enemy is free to pick what to do next, each option in its [`Brain`] gets a score:
+//! its weight times every one of its considerations. One option is then picked at random, with
+//! the odds weighted by score. A consideration reads one of the enemy's [`Input`]s, which are all
+//! between `0.0` and `1.0`, and maps it through a [`ResponseCurve`]:
+//!
+//! ```json
+//! {
+//!   "options": [
+//!     {
+//!       "action": "attack_player",
+//!       "weight": 0.6,
+//!       "considerations": [
+//!         { "input": "player_distance", "curve": { "linear": { "slope": -0.5, "intercept": 1.0 } } }
+//!       ]
+//!     },
+//!     {
+//!       "action": "move_anywhere",
+//!       "weight": 0.4,
+//!       "considerations": [
+//!         { "input": "just_moved", "curve": { "step": { "threshold": 0.5, "below": 1.0, "above": 0.0 } } }
+//!       ]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Each [`EnemyKind`](crate::parrylord::enemy::EnemyKind) has its own brain, so new enemies only
+//! need a new file. The [`Thinker`] keeps the last scores around for the dev tools to show.
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::io;
+
+pub fn plugin(app: &mut App) {
+    app.init_asset::<Brain>();
+    app.init_asset_loader::<BrainLoader>();
+    app.register_type::<Thinker>();
+}
+
+/// What an enemy can decide to do.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    MoveToPlayer,
+    MoveAnywhere,
+    AttackPlayer,
+    AttackAnywhere,
+    Idle,
+}
+
+/// What an enemy knows when deciding, each between `0.0` and `1.0`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[serde(rename_all = "snake_case")]
+pub enum Input {
+    /// How far away the player is, where `1.0` is [`Senses::FAR`] or further.
+    PlayerDistance,
+    /// How much of its health it has left.
+    Health,
+    /// `1.0` if it's just finished moving somewhere, `0.0` otherwise.
+    JustMoved,
+    /// How far into the run it is, where `1.0` is [`Senses::LATE_LEVEL`] or later.
+    Level,
+    /// How many other enemies there are, where `1.0` is [`Senses::CROWD`] or more.
+    Allies,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseCurve {
+    /// `slope * input + intercept`.
+    Linear { slope: f32, intercept: f32 },
+    /// `below` under the threshold, `above` at or over it.
+    Step {
+        threshold: f32,
+        below: f32,
+        above: f32,
+    },
+}
+
+impl ResponseCurve {
+    fn evaluate(self, input: f32) -> f32 {
+        let output = match self {
+            Self::Linear { slope, intercept } => slope.mul_add(input, intercept),
+            Self::Step {
+                threshold,
+                below,
+                above,
+            } => {
+                if input < threshold {
+                    below
+                } else {
+                    above
+                }
+            }
+        };
+
+        output.clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct Consideration {
+    pub input: Input,
+    pub curve: ResponseCurve,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct BrainOption {
+    pub action: Action,
+    pub weight: f32,
+    #[serde(default)]
+    pub considerations: Vec<Consideration>,
+}
+
+#[derive(Asset, TypePath, Deserialize, Debug, Clone, PartialEq)]
+pub struct Brain {
+    pub options: Vec<BrainOption>,
+}
+
+impl Brain {
+    /// How much the enemy wants to do each option right now.
+    pub fn scores(&self, senses: &Senses) -> Vec<(Action, f32)> {
+        self.options
+            .iter()
+            .map(|option| {
+                let score = option
+                    .considerations
+                    .iter()
+                    .map(|it| it.curve.evaluate(senses.input(it.input)))
+                    .fold(option.weight, |score, it| score * it);
+                (option.action, score)
+            })
+            .collect()
+    }
+}
+
+/// Picks an action at random, weighted by score, or `None` if nothing scored above zero.
+pub fn choose(scores: &[(Action, f32)], rng: &mut impl Rng) -> Option<Action> {
+    let total = scores.iter().map(|&(_, score)| score).sum::<f32>();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let mut roll = rng.r#gen::<f32>() * total;
+    for &(action, score) in scores {
+        if score <= 0.0 {
+            continue;
+        }
+        if roll < score {
+            return Some(action);
+        }
+        roll -= score;
+    }
+
+    // Rounding left some of the roll over.
+    scores
+        .iter()
+        .rev()
+        .find(|&&(_, score)| score > 0.0)
+        .map(|&(action, _)| action)
+}
+
+/// What an enemy knows about its situation, before being squashed into [`Input`]s.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Senses {
+    pub player_distance: f32,
+    /// Left, out of the maximum.
+    pub health: (u32, u32),
+    pub just_moved: bool,
+    pub level: u32,
+    pub allies: usize,
+}
+
+impl Senses {
+    pub const FAR: f32 = 1000.0;
+    pub const LATE_LEVEL: u32 = 30;
+    pub const CROWD: usize = 10;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn input(&self, input: Input) -> f32 {
+        let value = match input {
+            Input::PlayerDistance => self.player_distance / Self::FAR,
+            Input::Health => self.health.0 as f32 / self.health.1.max(1) as f32,
+            Input::JustMoved => f32::from(u8::from(self.just_moved)),
+            Input::Level => self.level as f32 / Self::LATE_LEVEL as f32,
+            Input::Allies => self.allies as f32 / Self::CROWD as f32,
+        };
+
+        value.clamp(0.0, 1.0)
+    }
+}
+
+/// Decides what an enemy does with its [`Brain`].
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Thinker {
+    pub brain: Handle<Brain>,
+    /// The last action picked.
+    pub thought: Option<Action>,
+    /// The scores it was picked from.
+    pub scores: Vec<(Action, f32)>,
+}
+
+impl Thinker {
+    pub fn new(brain: Handle<Brain>) -> Self {
+        Self {
+            brain,
+            thought: None,
+            scores: Vec::new(),
+        }
+    }
+
+    /// Scores every option and picks one, or `None` if the brain isn't loaded or nothing appeals.
+    pub fn decide(
+        &mut self,
+        brains: &Assets<Brain>,
+        senses: &Senses,
+        rng: &mut impl Rng,
+    ) -> Option<Action> {
+        let brain = brains.get(&self.brain)?;
+
+        self.scores = brain.scores(senses);
+        self.thought = choose(&self.scores, rng);
+        self.thought
+    }
+}
+
+#[derive(Default)]
+struct BrainLoader;
+
+impl AssetLoader for BrainLoader {
+    type Asset = Brain;
+    type Settings = ();
+    type Error = io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let brain: Brain = serde_json::from_slice(&bytes).map_err(io::Error::from)?;
+
+        if brain.options.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "brain has no options",
+            ));
+        }
+        if let Some(option) = brain.options.iter().find(|it| it.weight < 0.0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("option {:?} has a negative weight", option.action),
+            ));
+        }
+
+        Ok(brain)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ai.json"]
+    }
+}
@@ -0,0 +1,332 @@
+//! Sprite animations over a texture atlas, loaded from `.anim.json` files.
+//!
+//! A file names the sprite sheet and how it's split into frames, and then its clips:
+//!
+//! ```json
+//! {
+//!   "image": "images/pink/sheet.png",
+//!   "frame_size": [140, 200],
+//!   "columns": 6,
+//!   "rows": 1,
+//!   "clips": {
+//!     "walk": {
+//!       "frames": [2, 3],
+//!       "frame_time": 0.0833,
+//!       "looping": true,
+//!       "events": [{ "frame": 0, "name": "step" }]
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! [`SpriteAnimator`] plays them. It triggers [`AnimationEvent`] when a frame with an event starts,
+//! and [`AnimationFinished`] when a clip that doesn't loop ends. A `"step"` event kicks up a puff
+//! of dust.
+
+use crate::assets::AttackAssets;
+use crate::parrylord::ttl::Ttl;
+use crate::screens::Screen;
+use crate::zaphkiel::tween::{Tween, TweenTarget};
+use crate::{AppSystems, PausableSystems};
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::io;
+
+pub fn plugin(app: &mut App) {
+    app.init_asset::<SpriteAnimation>();
+    app.init_asset_loader::<SpriteAnimationLoader>();
+    app.register_type::<SpriteAnimator>();
+
+    app.add_observer(kick_up_dust);
+
+    app.add_systems(
+        Update,
+        animate_sprites
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+#[serde(rename_all = "snake_case")]
+pub enum Clip {
+    #[default]
+    Idle,
+    /// Standing still, but facing a direction.
+    Stand,
+    Walk,
+    Hurt,
+    Attack,
+    Death,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct AnimationClip {
+    /// Indices into the texture atlas.
+    pub frames: Vec<usize>,
+    /// How long each frame is shown, in seconds.
+    pub frame_time: f32,
+    #[serde(default)]
+    pub looping: bool,
+    #[serde(default)]
+    pub events: Vec<FrameEvent>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FrameEvent {
+    /// Position in [`AnimationClip::frames`].
+    pub frame: usize,
+    pub name: String,
+}
+
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct SpriteAnimation {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub clips: HashMap<Clip, AnimationClip>,
+}
+
+/// The contents of an `.anim.json` file.
+#[derive(Deserialize)]
+struct SpriteAnimationFile {
+    /// Path to the sprite sheet, from the assets folder.
+    image: String,
+    frame_size: [u32; 2],
+    columns: u32,
+    rows: u32,
+    clips: HashMap<Clip, AnimationClip>,
+}
+
+#[derive(Default)]
+struct SpriteAnimationLoader;
+
+impl AssetLoader for SpriteAnimationLoader {
+    type Asset = SpriteAnimation;
+    type Settings = ();
+    type Error = io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: SpriteAnimationFile = serde_json::from_slice(&bytes).map_err(io::Error::from)?;
+
+        let frame_count = (file.columns * file.rows) as usize;
+        for (clip, data) in &file.clips {
+            if data.frames.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("clip {clip:?} has no frames"),
+                ));
+            }
+            if let Some(frame) = data.frames.iter().find(|&&it| it >= frame_count) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("clip {clip:?} uses frame {frame}, but there are only {frame_count}"),
+                ));
+            }
+        }
+
+        let layout = TextureAtlasLayout::from_grid(
+            UVec2::from(file.frame_size),
+            file.columns,
+            file.rows,
+            None,
+            None,
+        );
+
+        Ok(SpriteAnimation {
+            image: load_context.load(file.image),
+            layout: load_context.add_labeled_asset(String::from("layout"), layout),
+            clips: file.clips,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["anim.json"]
+    }
+}
+
+/// Plays a [`SpriteAnimation`] on the entity's [`Sprite`].
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+#[require(Sprite)]
+pub struct SpriteAnimator {
+    pub animation: Handle<SpriteAnimation>,
+    clip: Clip,
+    /// Position in the clip's frames.
+    frame: usize,
+    /// Seconds since the current frame started.
+    elapsed: f32,
+    /// Whether the current frame's events have been triggered.
+    entered: bool,
+    finished: bool,
+}
+
+impl SpriteAnimator {
+    pub fn new(animation: Handle<SpriteAnimation>) -> Self {
+        Self {
+            animation,
+            clip: Clip::Idle,
+            frame: 0,
+            elapsed: 0.0,
+            entered: false,
+            finished: false,
+        }
+    }
+
+    pub const fn clip(&self) -> Clip {
+        self.clip
+    }
+
+    /// Whether a clip that doesn't loop has shown its last frame.
+    pub const fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Starts `clip` from the beginning, unless it's already playing.
+    pub fn play(&mut self, clip: Clip) {
+        if self.clip != clip {
+            self.clip = clip;
+            self.frame = 0;
+            self.elapsed = 0.0;
+            self.entered = false;
+            self.finished = false;
+        }
+    }
+}
+
+/// Triggered on an entity when its [`SpriteAnimator`] reaches a frame with a [`FrameEvent`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct AnimationEvent {
+    pub clip: Clip,
+    pub name: String,
+}
+
+impl AnimationEvent {
+    /// A foot touching the ground.
+    pub const STEP: &str = "step";
+}
+
+/// Triggered on an entity when its [`SpriteAnimator`] finishes a clip that doesn't loop.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationFinished(pub Clip);
+
+pub fn animate_sprites(
+    time: Res<Time>,
+    animations: Res<Assets<SpriteAnimation>>,
+    mut animators: Query<(Entity, &mut SpriteAnimator, &mut Sprite)>,
+    mut commands: Commands,
+) {
+    for (entity, mut animator, mut sprite) in &mut animators {
+        let Some(animation) = animations.get(&animator.animation) else {
+            continue;
+        };
+        let Some(clip) = animation.clips.get(&animator.clip) else {
+            warn_once!("an animation is missing the {:?} clip", animator.clip);
+            continue;
+        };
+
+        // In case the clip changed when the file was reloaded.
+        animator.frame = animator.frame.min(clip.frames.len() - 1);
+
+        if !animator.finished {
+            animator.elapsed += time.delta_secs();
+        }
+
+        while !animator.finished && animator.elapsed >= clip.frame_time {
+            animator.elapsed -= clip.frame_time;
+
+            if animator.frame + 1 < clip.frames.len() {
+                animator.frame += 1;
+                animator.entered = false;
+            } else if clip.looping {
+                animator.frame = 0;
+                animator.entered = false;
+            } else {
+                animator.finished = true;
+                commands.trigger_targets(AnimationFinished(animator.clip), entity);
+            }
+
+            // A frame time of zero would never catch up.
+            if clip.frame_time <= 0.0 {
+                animator.elapsed = 0.0;
+                break;
+            }
+        }
+
+        if !animator.entered {
+            animator.entered = true;
+            for event in clip.events.iter().filter(|it| it.frame == animator.frame) {
+                commands.trigger_targets(
+                    AnimationEvent {
+                        clip: animator.clip,
+                        name: event.name.clone(),
+                    },
+                    entity,
+                );
+            }
+        }
+
+        let atlas = TextureAtlas {
+            layout: animation.layout.clone(),
+            index: clip.frames[animator.frame],
+        };
+        if sprite.image != animation.image {
+            sprite.image = animation.image.clone();
+        }
+        if sprite.texture_atlas.as_ref() != Some(&atlas) {
+            sprite.texture_atlas = Some(atlas);
+        }
+    }
+}
+
+fn kick_up_dust(
+    trigger: Trigger<AnimationEvent>,
+    transforms: Query<&GlobalTransform>,
+    attack_assets: Res<AttackAssets>,
+    mut commands: Commands,
+) {
+    const LIFETIME: f32 = 0.3;
+    const COLOR: Color = Color::srgba(0.8, 0.75, 0.65, 0.4);
+    const FRAME: usize = 5;
+    // How far below the middle of a character its feet are.
+    const FEET: f32 = 50.0;
+
+    if trigger.event().name != AnimationEvent::STEP {
+        return;
+    }
+    let Ok(transform) = transforms.get(trigger.target()) else {
+        return;
+    };
+
+    let position = transform.translation().truncate() - Vec2::Y * FEET;
+
+    commands.spawn((
+        StateScoped(Screen::Gameplay),
+        Name::new("Dust"),
+        Sprite {
+            color: COLOR,
+            ..attack_assets.sprite(FRAME)
+        },
+        // Under the characters.
+        Transform::from_translation(position.extend(0.5)),
+        Ttl::new(LIFETIME),
+        Tween::new(
+            TweenTarget::Scale {
+                start: Vec3::splat(0.03),
+                end: Vec3::splat(0.1),
+            },
+            LIFETIME,
+        )
+        .with_ease(EaseFunction::QuadraticOut),
+    ));
+}
@@ -1,9 +1,144 @@
+use crate::assets::AttackAssets;
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Attack>();
+    app.register_type::<AttackPayload>();
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
 pub struct Attack(pub u32);
+
+/// Parrying `n` attacks at once makes one of power `2^(n-1)`, so each tier is one more attack
+/// parried.
+pub fn power_tier(power: u32) -> u32 {
+    power.max(1).ilog2()
+}
+
+/// What an attack does to what it hits, on top of the damage. See
+/// [`status`](crate::parrylord::status) for how the effects play out.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct AttackPayload {
+    /// How fast targets get pushed away, in world units per second.
+    pub knockback: f32,
+    /// How long targets can't act for, in seconds.
+    pub stun_secs: f32,
+    /// How much of their speed targets lose while slowed, from `0.0` to `1.0`.
+    pub slow: f32,
+    pub slow_secs: f32,
+    /// Damage targets take every [`Burning::TICK`](crate::parrylord::status::Burning::TICK)
+    /// seconds while burning.
+    pub burn: u32,
+    pub burn_secs: f32,
+    /// How many more enemies the attack goes through before it breaks.
+    pub pierce: u32,
+}
+
+impl AttackPayload {
+    const KNOCKBACK_PER_TIER: f32 = 80.0;
+    const SLOW_TIER: u32 = 2;
+    const SLOW: f32 = 0.5;
+    const SLOW_SECS: f32 = 1.5;
+    /// The first tier that staggers enemies.
+    const STAGGER_TIER: u32 = 3;
+    const STAGGER_SECS: f32 = 0.6;
+    const STAGGER_SECS_PER_TIER: f32 = 0.15;
+    const MAX_STAGGER_SECS: f32 = 2.0;
+    const BURN_TIER: u32 = 5;
+    const BURN_SECS: f32 = 2.0;
+    const PIERCE_TIER: u32 = 6;
+
+    /// The payload of a player attack of some power. Bigger parries knock enemies back further,
+    /// and past some tiers also slow, stagger, burn and pierce.
+    pub fn from_power(power: u32) -> Self {
+        let tier = power_tier(power);
+        #[allow(clippy::cast_precision_loss)]
+        let tier_f32 = tier as f32;
+
+        let mut payload = Self {
+            knockback: Self::KNOCKBACK_PER_TIER * tier_f32,
+            ..default()
+        };
+
+        if tier >= Self::SLOW_TIER {
+            payload.slow = Self::SLOW;
+            payload.slow_secs = Self::SLOW_SECS;
+        }
+        if tier >= Self::STAGGER_TIER {
+            #[allow(clippy::cast_precision_loss)]
+            let extra = (tier - Self::STAGGER_TIER) as f32;
+            payload.stun_secs = Self::STAGGER_SECS_PER_TIER
+                .mul_add(extra, Self::STAGGER_SECS)
+                .min(Self::MAX_STAGGER_SECS);
+        }
+        if tier >= Self::BURN_TIER {
+            payload.burn = power / 8;
+            payload.burn_secs = Self::BURN_SECS;
+        }
+        if tier >= Self::PIERCE_TIER {
+            payload.pierce = (tier - Self::PIERCE_TIER) / 2 + 1;
+        }
+
+        payload
+    }
+}
+
+/// How a player attack of some power looks. Each tier is bigger, brighter and glows more than the
+/// last, so stronger attacks stand out at a glance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PowerTier {
+    /// Index into [`AttackAssets::layout`].
+    pub frame: usize,
+    /// Multiplier on the sprite's size. The hitbox doesn't change.
+    pub size: f32,
+    pub color: Color,
+    /// Opacity of the halo drawn behind the attack, or `0.0` for none.
+    pub glow: f32,
+}
+
+impl PowerTier {
+    /// From blue for the weakest attacks, through cyan, violet and orange, to white.
+    const COLOR_RAMP: [Color; 5] = [
+        Color::srgb(0.1, 0.1, 30.0),
+        Color::srgb(0.1, 8.0, 30.0),
+        Color::srgb(20.0, 0.1, 30.0),
+        Color::srgb(30.0, 8.0, 0.1),
+        Color::srgb(30.0, 30.0, 30.0),
+    ];
+    /// How many tiers it takes to get from one color of the ramp to the next.
+    const TIERS_PER_COLOR: f32 = 2.0;
+    const SIZE_PER_TIER: f32 = 0.12;
+    const MAX_SIZE: f32 = 2.5;
+    /// The first tier with a halo.
+    const GLOW_TIER: u32 = 3;
+    const MAX_GLOW: f32 = 0.6;
+
+    pub fn from_power(power: u32) -> Self {
+        let tier = power_tier(power);
+        #[allow(clippy::cast_precision_loss)]
+        let tier_f32 = tier as f32;
+
+        let ramp = tier_f32 / Self::TIERS_PER_COLOR;
+        let last = Self::COLOR_RAMP.len() - 1;
+        let from = (ramp.floor() as usize).min(last);
+        let to = (from + 1).min(last);
+        let color = Self::COLOR_RAMP[from].mix(&Self::COLOR_RAMP[to], ramp.fract());
+
+        let glow = if tier < Self::GLOW_TIER {
+            0.0
+        } else {
+            (0.1 * (tier - Self::GLOW_TIER + 1) as f32).min(Self::MAX_GLOW)
+        };
+
+        Self {
+            frame: (tier as usize).min(AttackAssets::FRAMES - 1),
+            size: Self::SIZE_PER_TIER
+                .mul_add(tier_f32, 1.0)
+                .min(Self::MAX_SIZE),
+            color,
+            glow,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! A scripted run through a late level that reports frame times, for `--benchmark`.
+//!
+//! The player can't die, and parries at the closest enemy attack every few frames so that
+//! reflected attacks split and pile up like they would in a good run.
+
+use crate::parrylord::enemy_attack::EnemyAttack;
+use crate::parrylord::health::Health;
+use crate::parrylord::input::{read_player_input, PlayerInput};
+use crate::parrylord::player::Player;
+use crate::parrylord::projectile::{Projectile, ProjectilePool};
+use crate::screens::Screen;
+use crate::{AppSystems, PausableSystems};
+use bevy::diagnostic::FrameCount;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (
+            play_benchmark_input
+                .after(read_player_input)
+                .in_set(AppSystems::RecordInput),
+            keep_player_alive.in_set(AppSystems::Update),
+        )
+            .run_if(resource_exists::<Benchmark>)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+
+    app.add_systems(
+        Last,
+        record_frame_time
+            .run_if(resource_exists::<Benchmark>)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// The level the benchmark starts at, unless another one is given.
+pub const BENCHMARK_LEVEL: u32 = 30;
+
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Benchmark {
+    /// Frames to skip before measuring, while the level fills up.
+    warmup: u32,
+    /// Frames to measure.
+    frames: usize,
+    /// Real time each measured frame took, in seconds.
+    frame_times: Vec<f32>,
+    /// The most attacks live at once while measuring.
+    peak_projectiles: usize,
+}
+
+impl Benchmark {
+    pub fn new(warmup: u32, frames: usize) -> Self {
+        let frames = frames.max(1);
+        Self {
+            warmup,
+            frames,
+            frame_times: Vec::with_capacity(frames),
+            peak_projectiles: 0,
+        }
+    }
+
+    fn report(&self, pool: &ProjectilePool) {
+        let mut sorted = self.frame_times.clone();
+        sorted.sort_by(f32::total_cmp);
+
+        let percentile = |p: f32| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let index = ((sorted.len() - 1) as f32 * p).round() as usize;
+            sorted[index] * 1000.0
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let mean = sorted.iter().sum::<f32>() / sorted.len() as f32 * 1000.0;
+
+        info!(
+            frames = sorted.len(),
+            mean_ms = mean,
+            median_ms = percentile(0.5),
+            p99_ms = percentile(0.99),
+            max_ms = percentile(1.0),
+            peak_projectiles = self.peak_projectiles,
+            pooled_projectiles = pool.available(),
+            "benchmark finished"
+        );
+    }
+}
+
+fn play_benchmark_input(
+    mut input: ResMut<PlayerInput>,
+    frame_count: Res<FrameCount>,
+    time: Res<Time>,
+    player: Single<&GlobalTransform, With<Player>>,
+    enemy_attacks: Query<&GlobalTransform, With<EnemyAttack>>,
+) {
+    const PARRY_EVERY: u32 = 8;
+
+    let player = player.translation().truncate();
+    let closest = enemy_attacks
+        .iter()
+        .map(|it| it.translation().truncate())
+        .min_by(|a, b| {
+            a.distance_squared(player)
+                .total_cmp(&b.distance_squared(player))
+        });
+
+    *input = PlayerInput {
+        // Circle around, so the player isn't a sitting duck.
+        movement: Vec2::from_angle(time.elapsed_secs()),
+        cursor: closest,
+        parry: frame_count.0 % PARRY_EVERY == 0,
+    };
+}
+
+fn keep_player_alive(mut health: Single<&mut Health, With<Player>>) {
+    health.0 = u32::MAX;
+}
+
+fn record_frame_time(
+    mut benchmark: ResMut<Benchmark>,
+    real_time: Res<Time<Real>>,
+    projectiles: Query<(), With<Projectile>>,
+    pool: Res<ProjectilePool>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    if benchmark.warmup > 0 {
+        benchmark.warmup -= 1;
+        return;
+    }
+
+    benchmark.frame_times.push(real_time.delta_secs());
+    benchmark.peak_projectiles = benchmark.peak_projectiles.max(projectiles.iter().len());
+
+    if benchmark.frame_times.len() >= benchmark.frames {
+        benchmark.report(&pool);
+        app_exit.write(AppExit::Success);
+    }
+}
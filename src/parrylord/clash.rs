@@ -0,0 +1,213 @@
+//! Player attacks cancelling enemy attacks they run into.
+//!
+//! Each enemy attack cancelled costs the player attack as much power as the enemy attack had, and
+//! the player attack is gone once it runs out. A player attack that cancels
+//! [`VOLLEY_SIZE`](Clashes::VOLLEY_SIZE) enemy attacks clears a volley, which counts towards the
+//! score.
+
+use crate::assets::AttackAssets;
+use crate::audio::sound_effect;
+use crate::parrylord::attack::{Attack, PowerTier};
+use crate::parrylord::enemy_attack::EnemyAttack;
+use crate::parrylord::player_attack::PlayerAttack;
+use crate::parrylord::projectile::{
+    move_projectiles, FadingProjectile, Projectile, ProjectileMotion, ReleaseProjectile,
+};
+use crate::parrylord::ttl::Ttl;
+use crate::screens::Screen;
+use crate::telemetry::TelemetryEvent;
+use crate::zaphkiel::tween::{Tween, TweenTarget};
+use crate::{AppSystems, ParrylordSingleton, PausableSystems};
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use rand::prelude::SliceRandom;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Clashes>();
+
+    app.add_observer(play_clash_effects);
+
+    app.add_systems(
+        Update,
+        // Before anything else gets to see the cancelled attacks.
+        clash_attacks
+            .after(move_projectiles)
+            .before(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// How many enemy attacks a player attack has cancelled.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Clashes(pub u32);
+
+impl Clashes {
+    /// How many enemy attacks one player attack has to cancel to clear a volley.
+    pub const VOLLEY_SIZE: u32 = 5;
+}
+
+/// Triggered when a player attack cancels enemy attacks, for effects.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct Clash {
+    pub position: Vec2,
+    /// How many enemy attacks were cancelled at once.
+    pub cancelled: u32,
+    /// The power the player attack has left.
+    pub power: u32,
+}
+
+fn clash_attacks(
+    time: Res<Time>,
+    mut player_attacks: Query<
+        (
+            Entity,
+            &mut Attack,
+            &mut Clashes,
+            &Transform,
+            &ProjectileMotion,
+        ),
+        (With<PlayerAttack>, Without<FadingProjectile>),
+    >,
+    mut enemy_attacks: Query<
+        (Entity, &mut Attack, &Transform),
+        (
+            With<EnemyAttack>,
+            Without<PlayerAttack>,
+            Without<FadingProjectile>,
+        ),
+    >,
+    mut singleton: ResMut<ParrylordSingleton>,
+    mut telemetry: EventWriter<TelemetryEvent>,
+    mut commands: Commands,
+) {
+    // Attacks clash when their circles touch.
+    const REACH: f32 = Projectile::RADIUS * 2.0;
+
+    // Enemy attacks bucketed by position, so each player attack only checks the ones nearby.
+    let mut grid = HashMap::<IVec2, Vec<Entity>>::default();
+    for (entity, _, transform) in &enemy_attacks {
+        grid.entry(cell(transform.translation.truncate()))
+            .or_default()
+            .push(entity);
+    }
+    if grid.is_empty() {
+        return;
+    }
+
+    for (player_attack, mut power, mut clashes, transform, motion) in &mut player_attacks {
+        let end = transform.translation.truncate();
+        let start = end - motion.velocity * time.delta_secs();
+
+        let min = cell(start.min(end) - REACH);
+        let max = cell(start.max(end) + REACH);
+
+        let mut cancelled = 0;
+        'cells: for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                let Some(nearby) = grid.get(&IVec2::new(x, y)) else {
+                    continue;
+                };
+
+                for &enemy_attack in nearby {
+                    let Ok((_, mut enemy_power, enemy_transform)) =
+                        enemy_attacks.get_mut(enemy_attack)
+                    else {
+                        continue;
+                    };
+                    if enemy_power.0 == 0 {
+                        // Already cancelled by another player attack.
+                        continue;
+                    }
+
+                    let position = enemy_transform.translation.truncate();
+                    if distance_to_segment(position, start, end) > REACH {
+                        continue;
+                    }
+
+                    let cost = power.0.min(enemy_power.0);
+                    power.0 -= cost;
+                    enemy_power.0 -= cost;
+
+                    if enemy_power.0 == 0 {
+                        commands.trigger_targets(ReleaseProjectile, enemy_attack);
+                        cancelled += 1;
+                    }
+                    if power.0 == 0 {
+                        commands.trigger_targets(ReleaseProjectile, player_attack);
+                        break 'cells;
+                    }
+                }
+            }
+        }
+
+        if cancelled == 0 {
+            continue;
+        }
+
+        let cleared_before = clashes.0 >= Clashes::VOLLEY_SIZE;
+        clashes.0 += cancelled;
+        if !cleared_before && clashes.0 >= Clashes::VOLLEY_SIZE {
+            singleton.volleys_cleared += 1;
+            telemetry.write(TelemetryEvent::VolleyCleared {
+                volleys_cleared: singleton.volleys_cleared,
+            });
+        }
+
+        commands.trigger(Clash {
+            position: end,
+            cancelled,
+            power: power.0,
+        });
+    }
+}
+
+fn cell(position: Vec2) -> IVec2 {
+    const CELL_SIZE: f32 = 64.0;
+    (position / CELL_SIZE).floor().as_ivec2()
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let t = (point - start).dot(segment) / segment.length_squared().max(f32::EPSILON);
+    point.distance(start + segment * t.clamp(0.0, 1.0))
+}
+
+fn play_clash_effects(
+    trigger: Trigger<Clash>,
+    attack_assets: Res<AttackAssets>,
+    mut commands: Commands,
+) {
+    const LIFETIME: f32 = 0.2;
+
+    let clash = trigger.event();
+    let tier = PowerTier::from_power(clash.power);
+
+    commands.spawn((
+        StateScoped(Screen::Gameplay),
+        Name::new("Clash"),
+        Sprite {
+            color: Color::WHITE,
+            ..attack_assets.sprite(tier.frame)
+        },
+        Transform::from_translation(clash.position.extend(4.0)),
+        Ttl::new(LIFETIME),
+        Tween::new(
+            TweenTarget::Scale {
+                start: Vec3::splat(0.05),
+                end: Vec3::splat(0.2),
+            },
+            LIFETIME,
+        )
+        .with_ease(EaseFunction::QuadraticOut),
+    ));
+
+    commands.spawn(sound_effect(
+        attack_assets
+            .parry_sfx
+            .choose(&mut rand::thread_rng())
+            .expect("should be valid")
+            .clone(),
+    ));
+}
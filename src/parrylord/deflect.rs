@@ -0,0 +1,292 @@
+//! Elite enemies parrying player attacks back.
+//!
+//! From [`ELITE_LEVEL`](Deflector::ELITE_LEVEL) on, some enemies spawn as elites with a
+//! [`Deflector`]. A player attack that hits one has a chance to come straight back at the player
+//! as an enemy attack with the same power, after which the elite has to wait a moment before it
+//! can do it again. Parrying it back keeps the [`Rally`] going, and the longest rally of a run
+//! counts towards the score.
+
+use crate::assets::AttackAssets;
+use crate::audio::SoundEffect;
+use crate::parrylord::attack::Attack;
+use crate::parrylord::enemy::Enemy;
+use crate::parrylord::enemy_attack::EnemyAttack;
+use crate::parrylord::health::InvincibilityTimer;
+use crate::parrylord::player::Player;
+use crate::parrylord::player_attack::{deal_damage, PlayerAttack};
+use crate::parrylord::projectile::{
+    ProjectileHits, ProjectileMotion, ProjectilePool, ReleaseProjectile,
+};
+use crate::parrylord::rng::GameRng;
+use crate::parrylord::spawning::Spawning;
+use crate::parrylord::status::CanAct;
+use crate::parrylord::ttl::Ttl;
+use crate::screens::Screen;
+use crate::telemetry::TelemetryEvent;
+use crate::zaphkiel::tween::{Tween, TweenTarget};
+use crate::{AppSystems, ParrylordSingleton, PausableSystems};
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use rand::prelude::SliceRandom;
+use rand::Rng;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Deflector>();
+    app.register_type::<EliteHalo>();
+    app.register_type::<Rally>();
+
+    app.add_observer(play_rally_effects);
+
+    app.add_systems(
+        Update,
+        (
+            tick_deflectors.in_set(AppSystems::TickTimers),
+            // Both draw from the `GameRng`, so their order must not change between runs.
+            deflect_player_attacks
+                .in_set(AppSystems::Update)
+                .before(deal_damage),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// An elite enemy that can send player attacks back.
+#[derive(Component, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Deflector {
+    /// The odds of sending back an attack that hits it.
+    pub chance: f64,
+    /// How long it has to wait after sending one back.
+    pub cooldown: Timer,
+}
+
+impl Deflector {
+    /// The first level elites spawn on.
+    pub const ELITE_LEVEL: u32 = 5;
+    /// The odds of an enemy being an elite.
+    const ELITE_CHANCE: f64 = 0.2;
+    const BASE_CHANCE: f64 = 0.25;
+    /// How much likelier elites get to send attacks back every level.
+    const CHANCE_PER_LEVEL: f64 = 0.01;
+    const MAX_CHANCE: f64 = 0.5;
+    const COOLDOWN_SECS: f32 = 1.5;
+    /// The least time attacks that got sent back have to reach the player, in seconds.
+    const MIN_TTL: f32 = 2.0;
+
+    /// Whether an enemy spawning on `level` is an elite, and if so, its [`Deflector`].
+    ///
+    /// Doesn't draw from `rng` before [`Self::ELITE_LEVEL`].
+    pub fn roll(level: u32, rng: &mut impl Rng) -> Option<Self> {
+        if level < Self::ELITE_LEVEL || !rng.gen_bool(Self::ELITE_CHANCE) {
+            return None;
+        }
+
+        let chance = Self::CHANCE_PER_LEVEL
+            .mul_add(f64::from(level - Self::ELITE_LEVEL), Self::BASE_CHANCE)
+            .min(Self::MAX_CHANCE);
+
+        // Ready straight away.
+        let mut cooldown = Timer::from_seconds(Self::COOLDOWN_SECS, TimerMode::Once);
+        cooldown.tick(cooldown.duration());
+
+        Some(Self { chance, cooldown })
+    }
+}
+
+/// The glow behind an elite.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct EliteHalo;
+
+impl EliteHalo {
+    const COLOR: Color = Color::srgba(1.0, 0.8, 0.2, 0.7);
+    const SIZE: f32 = 0.6;
+    const FRAME: usize = 6;
+
+    pub fn bundle(attack_assets: &AttackAssets) -> impl Bundle {
+        (
+            Name::new("EliteHalo"),
+            Self,
+            Sprite {
+                color: Self::COLOR,
+                ..attack_assets.sprite(Self::FRAME)
+            },
+            // Behind the enemy.
+            Transform::from_xyz(0.0, 0.0, -0.5).with_scale(Vec3::splat(Self::SIZE)),
+        )
+    }
+}
+
+/// How many times an attack has been sent back and forth between the player and elites.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Rally(pub u32);
+
+impl Rally {
+    /// Counts the attack being sent back once more, returning the new [`Rally`].
+    pub fn extend(
+        rally: Option<&Self>,
+        singleton: &mut ParrylordSingleton,
+        telemetry: &mut EventWriter<TelemetryEvent>,
+    ) -> Self {
+        let rally = Self(rally.map_or(0, |it| it.0) + 1);
+
+        singleton.longest_rally = singleton.longest_rally.max(rally.0);
+        telemetry.write(TelemetryEvent::Rally {
+            length: rally.0,
+            longest_rally: singleton.longest_rally,
+        });
+
+        rally
+    }
+}
+
+/// Triggered when an attack gets sent back as part of a [`Rally`], for effects.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct RallyHit {
+    pub position: Vec2,
+    pub rally: u32,
+}
+
+fn tick_deflectors(mut deflectors: Query<&mut Deflector>, time: Res<Time>) {
+    for mut deflector in &mut deflectors {
+        deflector.cooldown.tick(time.delta());
+    }
+}
+
+fn deflect_player_attacks(
+    attacks: Query<
+        (
+            Entity,
+            &ProjectileHits,
+            &Attack,
+            &Transform,
+            &ProjectileMotion,
+            &Ttl,
+            Option<&Rally>,
+        ),
+        (With<PlayerAttack>, Without<InvincibilityTimer>),
+    >,
+    mut deflectors: Query<
+        &mut Deflector,
+        (
+            With<Enemy>,
+            CanAct,
+            Without<Spawning>,
+            Without<InvincibilityTimer>,
+        ),
+    >,
+    player: Query<&GlobalTransform, With<Player>>,
+    attack_assets: Res<AttackAssets>,
+    mut pool: ResMut<ProjectilePool>,
+    mut rng: ResMut<GameRng>,
+    mut singleton: ResMut<ParrylordSingleton>,
+    mut telemetry: EventWriter<TelemetryEvent>,
+    mut commands: Commands,
+) {
+    for (attack_entity, hits, attack, transform, motion, ttl, rally) in &attacks {
+        for &entity in hits.iter() {
+            let Ok(mut deflector) = deflectors.get_mut(entity) else {
+                continue;
+            };
+            if !deflector.cooldown.finished() || !rng.0.gen_bool(deflector.chance) {
+                continue;
+            }
+            deflector.cooldown.reset();
+
+            let position = transform.translation.truncate();
+            let direction = player
+                .single()
+                .map_or(-motion.velocity, |it| {
+                    it.translation().truncate() - position
+                })
+                .normalize_or(Vec2::X);
+            let velocity = direction * motion.velocity.length();
+            let ttl = Ttl::new(ttl.0.remaining_secs().max(Deflector::MIN_TTL));
+
+            let rally = Rally::extend(rally, &mut singleton, &mut telemetry);
+            pool.spawn(
+                &mut commands,
+                (
+                    EnemyAttack::bundle(&attack_assets, position, velocity, ttl, attack.0),
+                    rally,
+                ),
+            );
+
+            // Gone before it gets to deal damage.
+            commands.trigger_targets(ReleaseProjectile, attack_entity);
+            commands.trigger(RallyHit {
+                position,
+                rally: rally.0,
+            });
+            break;
+        }
+    }
+}
+
+fn play_rally_effects(
+    trigger: Trigger<RallyHit>,
+    attack_assets: Res<AttackAssets>,
+    mut commands: Commands,
+) {
+    const LIFETIME: f32 = 0.3;
+    const COLOR: Color = Color::srgb(30.0, 24.0, 4.0);
+    // Every hit of a rally sounds a bit higher than the last.
+    const SPEED_PER_HIT: f32 = 0.15;
+    const MAX_SPEED: f32 = 2.5;
+    // A rally rings out over the parry, an octave up, so it doesn't sound like a normal one.
+    const RING_SPEED: f32 = 2.0;
+
+    let hit = trigger.event();
+
+    commands.spawn((
+        StateScoped(Screen::Gameplay),
+        Name::new("RallyHit"),
+        Sprite {
+            color: COLOR,
+            ..attack_assets.sprite(EliteHalo::FRAME)
+        },
+        Transform::from_translation(hit.position.extend(4.0)),
+        Ttl::new(LIFETIME),
+        Tween::new(
+            TweenTarget::Scale {
+                start: Vec3::splat(0.1),
+                end: Vec3::splat(0.4),
+            },
+            LIFETIME,
+        )
+        .with_ease(EaseFunction::QuadraticOut),
+    ));
+
+    #[allow(clippy::cast_precision_loss)]
+    let speed = SPEED_PER_HIT.mul_add(hit.rally as f32, 1.0).min(MAX_SPEED);
+    let mut rng = rand::thread_rng();
+
+    commands.spawn((
+        AudioPlayer(
+            attack_assets
+                .parry_sfx
+                .choose(&mut rng)
+                .expect("should be valid")
+                .clone(),
+        ),
+        PlaybackSettings::DESPAWN
+            .with_speed(speed)
+            .with_volume(Volume::Linear(1.2)),
+        SoundEffect,
+    ));
+    commands.spawn((
+        AudioPlayer(
+            attack_assets
+                .attack_sfx
+                .choose(&mut rng)
+                .expect("should be valid")
+                .clone(),
+        ),
+        PlaybackSettings::DESPAWN
+            .with_speed(speed * RING_SPEED)
+            .with_volume(Volume::Linear(0.8)),
+        SoundEffect,
+    ));
+}
@@ -1,8 +1,10 @@
 // https://github.com/Jondolf/avian/blob/main/crates/avian2d/examples/dynamic_character_2d/plugin.rs
 
+use crate::parrylord::input::PlayerInput;
 use crate::parrylord::CollisionLayer;
 use crate::screens::Screen;
-use crate::{PausableSystems, exponential_decay};
+use crate::zaphkiel::smoothing::Spring;
+use crate::{AppSystems, PausableSystems};
 use avian2d::math::{AdjustPrecision, Scalar, Vector};
 use avian2d::prelude::*;
 use bevy::prelude::*;
@@ -12,9 +14,10 @@ pub fn plugin(app: &mut App) {
 
     app.add_systems(
         Update,
-        (keyboard_input, movement)
+        (movement_input, movement)
             .chain()
             .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
 }
@@ -46,16 +49,29 @@ pub struct CharacterControllerBundle {
     movement: MovementBundle,
 }
 
+/// Smooths the velocity towards where the input is pointing.
+#[derive(Component)]
+pub struct MovementSpring(Spring<Vector>);
+
+impl MovementSpring {
+    /// About how long it takes to get up to speed, in seconds.
+    const START_TIME: Scalar = 0.12;
+    /// Stop faster than we speed up.
+    const STOP_TIME: Scalar = 0.06;
+}
+
 /// A bundle that contains components for character movement.
 #[derive(Bundle)]
 pub struct MovementBundle {
     max_speed: MaxMovementSpeed,
+    spring: MovementSpring,
 }
 
 impl MovementBundle {
-    pub const fn new(speed: Scalar) -> Self {
+    pub fn new(speed: Scalar) -> Self {
         Self {
             max_speed: MaxMovementSpeed(speed),
+            spring: MovementSpring(Spring::new(MovementSpring::START_TIME)),
         }
     }
 }
@@ -86,19 +102,12 @@ impl CharacterControllerBundle {
     }
 }
 
-/// Sends [`MovementAction`] events based on keyboard input.
-fn keyboard_input(
+/// Sends [`MovementAction`] events based on [`PlayerInput`].
+fn movement_input(
     mut movement_event_writer: EventWriter<MovementAction>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    player_input: Res<PlayerInput>,
 ) {
-    let left = keyboard_input.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]);
-    let right = keyboard_input.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]);
-    let up = keyboard_input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]);
-    let down = keyboard_input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]);
-
-    let horizontal = f32::from(i8::from(right) - i8::from(left));
-    let vertical = f32::from(i8::from(up) - i8::from(down));
-    let direction = Vector::new(horizontal, vertical);
+    let direction = player_input.movement;
 
     movement_event_writer.write(if direction.length_squared() == 0.0 {
         MovementAction::None
@@ -111,37 +120,28 @@ fn keyboard_input(
 fn movement(
     time: Res<Time>,
     mut movement_event_reader: EventReader<MovementAction>,
-    mut controllers: Query<(&MaxMovementSpeed, &mut LinearVelocity)>,
+    mut controllers: Query<(&MaxMovementSpeed, &mut MovementSpring, &mut LinearVelocity)>,
 ) {
     // Precision is adjusted so that the example works with
     // both the `f32` and `f64` features. Otherwise you don't need this.
     let delta_time = time.delta_secs_f64().adjust_precision();
 
     for event in movement_event_reader.read() {
-        for (max_speed, mut linear_velocity) in &mut controllers {
-            match event {
+        for (max_speed, mut spring, mut linear_velocity) in &mut controllers {
+            let (target, smooth_time) = match event {
                 MovementAction::Move(direction) => {
-                    let curr = linear_velocity.0;
-                    let to_add = *direction * max_speed.0;
-                    let target = curr + to_add;
-
-                    let actual =
-                        exponential_decay!(current: curr, target: target, delta: delta_time)
-                            .clamp_length_max(max_speed.0);
-
-                    *linear_velocity = LinearVelocity(actual);
+                    (*direction * max_speed.0, MovementSpring::START_TIME)
                 }
-                MovementAction::None => {
-                    let curr = linear_velocity.0;
-                    let target = Vector::ZERO;
+                MovementAction::None => (Vector::ZERO, MovementSpring::STOP_TIME),
+            };
 
-                    let actual =
-                        exponential_decay!(current: curr, target: target, decay: 30.0 ,delta: delta_time)
-                            .clamp_length_min(0.0);
+            spring.0.smooth_time = smooth_time;
+            let actual = spring
+                .0
+                .update(linear_velocity.0, target, delta_time)
+                .clamp_length_max(max_speed.0);
 
-                    *linear_velocity = LinearVelocity(actual);
-                }
-            }
+            *linear_velocity = LinearVelocity(actual);
         }
     }
 }
@@ -1,27 +1,49 @@
 use crate::assets::{AttackAssets, EnemyAssets};
 use crate::audio::sound_effect;
+use crate::parrylord::ai::{Action, Brain, Senses, Thinker};
+use crate::parrylord::animation::SpriteAnimation;
+use crate::parrylord::animation::{animate_sprites, AnimationFinished, Clip, SpriteAnimator};
+use crate::parrylord::deflect::{Deflector, EliteHalo};
 use crate::parrylord::enemy_attack::EnemyAttack;
-use crate::parrylord::health::{DisplayHealth, Health, ZeroHealth};
+use crate::parrylord::health::{DisplayHealth, Health, InvincibilityTimer, MaxHealth, ZeroHealth};
+use crate::parrylord::homing::Homing;
 use crate::parrylord::player::Player;
+use crate::parrylord::player_attack::deal_damage;
+use crate::parrylord::projectile::ProjectilePool;
+use crate::parrylord::rng::GameRng;
+use crate::parrylord::spawning::{Portal, SpawnDirector, Spawning};
+use crate::parrylord::status::{CanAct, Knockback, Slowed, Stunned};
+use crate::parrylord::steering::{Steering, SteeringBehaviour};
+use crate::parrylord::telegraph::Telegraph;
 use crate::parrylord::ttl::Ttl;
 use crate::parrylord::CollisionLayer;
 use crate::screens::Screen;
+use crate::telemetry::TelemetryEvent;
 use crate::{AppSystems, ParrylordSingleton, PausableSystems};
 use avian2d::prelude::{AngularVelocity, Collider, CollisionLayers, LinearVelocity, RigidBody};
 use bevy::prelude::*;
 use rand::prelude::SliceRandom;
-use rand::{random, Rng};
+use rand::Rng;
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Enemy>();
+    app.register_type::<EnemyKind>();
     app.register_type::<EnemyAssets>();
     app.add_event::<EnemyIntent>();
     app.add_event::<SpawnEnemy>();
     app.register_type::<EnemyStateTimer>();
 
+    app.add_observer(interrupt_stunned_enemies);
+    app.add_observer(flinch_when_hit);
+
     app.add_systems(
         Update,
-        (handle_dead_enemies, write_enemy_intents)
+        (
+            handle_dead_enemies,
+            // Both draw from the `GameRng`, so their order must not change between runs.
+            write_enemy_intents.after(deal_damage),
+            choose_enemy_clip.before(animate_sprites),
+        )
             .run_if(in_state(Screen::Gameplay))
             .in_set(PausableSystems),
     );
@@ -29,6 +51,7 @@ pub fn plugin(app: &mut App) {
     app.add_systems(
         PreUpdate,
         (handle_enemy_intents, handle_spawn_enemy_events)
+            .chain()
             .run_if(in_state(Screen::Gameplay))
             .in_set(PausableSystems),
     );
@@ -53,14 +76,50 @@ pub fn handle_spawn_enemy_events(
     mut events: EventReader<SpawnEnemy>,
     mut commands: Commands,
     enemy_assets: Res<EnemyAssets>,
+    attack_assets: Res<AttackAssets>,
     singleton: Res<ParrylordSingleton>,
+    director: Res<SpawnDirector>,
+    player: Query<&GlobalTransform, With<Player>>,
+    enemies: Query<&Transform, With<Enemy>>,
+    mut rng: ResMut<GameRng>,
+    mut telemetry: EventWriter<TelemetryEvent>,
 ) {
+    let player = player
+        .single()
+        .map_or(Vec2::ZERO, |it| it.translation().truncate());
+    let mut taken = enemies
+        .iter()
+        .map(|it| it.translation.truncate())
+        .collect::<Vec<_>>();
+
     for _ in events.read() {
-        commands.spawn(Enemy::bundle(
-            &enemy_assets,
-            get_random_vec2_in_play_area(),
-            Enemy::BASE_HEALTH.saturating_pow(singleton.level - 1),
+        let position = director.position(singleton.level, &mut rng.0, player, &taken);
+        taken.push(position);
+        let health = Enemy::BASE_HEALTH.saturating_pow(singleton.level - 1);
+
+        let mut enemy = commands.spawn((
+            Enemy::bundle(&enemy_assets, &mut rng.0, position, health),
+            Spawning::bundle(Enemy::SCALE, director.portal_secs),
         ));
+        let deflector = Deflector::roll(singleton.level, &mut rng.0);
+        let elite = deflector.is_some();
+        if let Some(deflector) = deflector {
+            enemy
+                .insert(deflector)
+                .with_child(EliteHalo::bundle(&attack_assets));
+        }
+
+        commands.spawn(Portal::bundle(
+            &attack_assets,
+            position,
+            director.portal_secs,
+        ));
+
+        telemetry.write(TelemetryEvent::EnemySpawned {
+            position: position.to_array(),
+            health,
+            elite,
+        });
     }
 }
 
@@ -69,6 +128,8 @@ pub fn handle_spawn_enemy_events(
 pub enum EnemyState {
     Start,
     MovingTo(Vec2),
+    /// Telegraphing a volley of attacks at a position.
+    WindingUp(Vec2, u8),
     Attacking(u8),
     #[default]
     Idling,
@@ -80,6 +141,9 @@ pub enum EnemyIntent {
     None,
     Idle(Entity),
     Move(Entity, Vec2),
+    /// Go for the player at full speed.
+    Chase(Entity, Vec2),
+    WindUp(Entity, Vec2, u8),
     Attack(Entity, Vec2, u8),
     GoToStart(Entity),
 }
@@ -89,105 +153,130 @@ impl EnemyIntent {
     fn get_entity(&self) -> Option<Entity> {
         match self {
             Self::None => None,
-            Self::Idle(it) | Self::Move(it, _) | Self::Attack(it, _, _) | Self::GoToStart(it) => {
-                Some(*it)
-            }
+            Self::Idle(it)
+            | Self::Move(it, _)
+            | Self::Chase(it, _)
+            | Self::WindUp(it, _, _)
+            | Self::Attack(it, _, _)
+            | Self::GoToStart(it) => Some(*it),
         }
     }
 }
 
 #[tracing::instrument(skip_all)]
 pub fn write_enemy_intents(
-    mut enemies: Query<(
-        &GlobalTransform,
-        &mut Transform,
-        &mut LinearVelocity,
-        &mut AngularVelocity,
-        &mut EnemyStateTimer,
-        &mut Enemy,
-        Entity,
-    )>,
+    mut enemies: Query<
+        (
+            &GlobalTransform,
+            &mut Transform,
+            &mut AngularVelocity,
+            &mut EnemyStateTimer,
+            &mut Enemy,
+            &mut Thinker,
+            Option<(&Health, &MaxHealth)>,
+            Option<&Telegraph>,
+            Entity,
+        ),
+        (CanAct, Without<Spawning>),
+    >,
+    all_enemies: Query<(), With<Enemy>>,
     player: Single<&GlobalTransform, With<Player>>,
+    brains: Res<Assets<Brain>>,
     mut intent_writer: EventWriter<EnemyIntent>,
     singleton: Res<ParrylordSingleton>,
+    mut rng: ResMut<GameRng>,
 ) {
-    for (global_transform, mut transform, mut velocity, mut spin, timer, enemy, entity) in
-        &mut enemies
+    let allies = all_enemies.iter().len().saturating_sub(1);
+
+    for (
+        global_transform,
+        mut transform,
+        mut spin,
+        timer,
+        enemy,
+        mut thinker,
+        health,
+        telegraph,
+        entity,
+    ) in &mut enemies
     {
         let Enemy(state) = *enemy;
         let player_pos = player.translation().truncate();
+        let my_position = global_transform.translation().truncate();
         let timer_expired = timer.0.just_finished();
-        let mut thread_rng = rand::thread_rng();
-        let offset = (random::<Vec2>() * 2.0 - Vec2::splat(1.0)) * 30.0;
+        let offset = (rng.r#gen::<Vec2>() * 2.0 - Vec2::splat(1.0)) * 30.0;
         let no_of_attacks =
-            u8::try_from(thread_rng.gen_range(1..=(4 + singleton.level))).unwrap_or(u8::MAX);
+            u8::try_from(rng.gen_range(1..=(4 + singleton.level))).unwrap_or(u8::MAX);
+
+        let mut decide = |just_moved: bool| {
+            let senses = Senses {
+                player_distance: my_position.distance(player_pos),
+                health: health.map_or((1, 1), |(health, max_health)| (health.0, max_health.0)),
+                just_moved,
+                level: singleton.level,
+                allies,
+            };
+
+            match thinker.decide(&brains, &senses, &mut rng.0) {
+                Some(Action::MoveToPlayer) => EnemyIntent::Chase(entity, player_pos + offset),
+                Some(Action::MoveAnywhere) => {
+                    EnemyIntent::Move(entity, get_random_vec2_in_play_area(&mut rng.0) + offset)
+                }
+                Some(Action::AttackPlayer) => {
+                    EnemyIntent::WindUp(entity, player_pos + offset, no_of_attacks)
+                }
+                Some(Action::AttackAnywhere) => EnemyIntent::WindUp(
+                    entity,
+                    get_random_vec2_in_play_area(&mut rng.0) + offset,
+                    no_of_attacks,
+                ),
+                Some(Action::Idle) | None => EnemyIntent::Idle(entity),
+            }
+        };
 
-        let id = match state {
+        let intent = match state {
             EnemyState::Start => {
                 transform.rotation = Quat::IDENTITY;
-                *velocity = LinearVelocity::ZERO;
                 *spin = AngularVelocity::ZERO;
 
-                let travel = thread_rng.gen_bool(0.5);
-                if travel {
-                    let to_player = thread_rng.gen_bool(0.5);
-                    let pos = if to_player {
-                        player_pos + offset
-                    } else {
-                        get_random_vec2_in_play_area() + offset
-                    };
-
-                    intent_writer.write(EnemyIntent::Move(entity, pos))
-                } else {
-                    let to_player = thread_rng.gen_bool(0.9);
-                    let pos = if to_player {
-                        player_pos + offset
-                    } else {
-                        get_random_vec2_in_play_area() + offset
-                    };
-
-                    intent_writer.write(EnemyIntent::Attack(entity, pos, no_of_attacks))
-                }
+                decide(false)
             }
             EnemyState::MovingTo(pos) => {
-                let my_position = global_transform.translation().truncate();
                 let reached_destination = pos.distance_squared(my_position) < 500.0;
 
                 if timer_expired || reached_destination {
-                    let to_player = thread_rng.gen_bool(0.9);
-
-                    let pos = if to_player {
-                        player_pos + offset
-                    } else {
-                        get_random_vec2_in_play_area() + offset
-                    };
-
-                    intent_writer.write(EnemyIntent::Attack(entity, pos, no_of_attacks))
+                    decide(true)
+                } else {
+                    EnemyIntent::None
+                }
+            }
+            EnemyState::WindingUp(pos, n) => {
+                if telegraph.is_none_or(Telegraph::is_finished) {
+                    EnemyIntent::Attack(entity, pos, n)
                 } else {
-                    intent_writer.write(EnemyIntent::None)
+                    EnemyIntent::None
                 }
             }
             EnemyState::Attacking(n) => {
                 if n != 0 {
-                    intent_writer.write(EnemyIntent::Attack(entity, player_pos, n - 1))
+                    EnemyIntent::Attack(entity, player_pos, n - 1)
                 } else {
-                    intent_writer.write(EnemyIntent::Idle(entity))
+                    EnemyIntent::Idle(entity)
                 }
             }
             EnemyState::Idling => {
                 transform.rotation = Quat::IDENTITY;
-                *velocity = LinearVelocity::ZERO;
                 *spin = AngularVelocity::ZERO;
 
                 if timer_expired {
-                    intent_writer.write(EnemyIntent::GoToStart(entity))
+                    EnemyIntent::GoToStart(entity)
                 } else {
-                    intent_writer.write(EnemyIntent::None)
+                    EnemyIntent::None
                 }
             }
         };
 
-        _ = id;
+        intent_writer.write(intent);
     }
 }
 
@@ -196,10 +285,14 @@ pub fn handle_enemy_intents(
     mut intents: EventReader<EnemyIntent>,
     mut enemies: Query<(
         &mut Enemy,
-        (&GlobalTransform, &mut LinearVelocity, &mut EnemyStateTimer),
+        &EnemyKind,
+        (&GlobalTransform, &mut Steering, &mut EnemyStateTimer),
     )>,
     mut commands: Commands,
     attack_assets: Res<AttackAssets>,
+    mut rng: ResMut<GameRng>,
+    mut pool: ResMut<ProjectilePool>,
+    singleton: Res<ParrylordSingleton>,
 ) -> Result {
     if intents.is_empty() {
         return Ok(());
@@ -215,7 +308,7 @@ pub fn handle_enemy_intents(
             continue;
         };
 
-        let Ok((mut enemy_state, (global_transform, mut velocity, _timer))) =
+        let Ok((mut enemy_state, kind, (global_transform, mut steering, _timer))) =
             enemies.get_mut(enemy)
         else {
             warn!(
@@ -226,30 +319,53 @@ pub fn handle_enemy_intents(
         };
         enemy_state.0 = match intent {
             EnemyIntent::None => EnemyState::Start,
-            EnemyIntent::Idle(_) => EnemyState::Idling,
+            EnemyIntent::Idle(_) => {
+                steering.behaviour = SteeringBehaviour::Orbit(Enemy::ORBIT_RANGE);
+                EnemyState::Idling
+            }
             EnemyIntent::Move(_, pos) => {
-                let curr = global_transform.translation();
-                let target = pos.extend(curr.z);
-
-                let dir = (target - curr).normalize();
-                let target = dir * Enemy::SPEED;
+                steering.behaviour = SteeringBehaviour::Arrive(pos);
+                EnemyState::MovingTo(pos)
+            }
+            EnemyIntent::Chase(_, pos) => {
+                steering.behaviour = SteeringBehaviour::Seek(pos);
+                EnemyState::MovingTo(pos)
+            }
+            EnemyIntent::WindUp(_, pos, n) => {
+                // Stand still, so the aim line stays honest.
+                steering.behaviour = SteeringBehaviour::Stop;
 
-                *velocity = LinearVelocity::from(target.truncate());
+                Telegraph::start(
+                    &mut commands,
+                    &attack_assets,
+                    enemy,
+                    pos,
+                    kind.wind_up_secs(),
+                );
 
-                EnemyState::MovingTo(pos)
+                EnemyState::WindingUp(pos, n)
             }
             EnemyIntent::Attack(_, pos, n) => {
+                commands.entity(enemy).remove::<Telegraph>();
+
                 let my_pos = global_transform.translation().truncate();
-                let velocity = LinearVelocity(
-                    (pos - my_pos).normalize() * 500.0
-                        + (random::<f32>().mul_add(2.0, -1.0) * 150.0),
+                let velocity = (pos - my_pos).normalize() * 500.0
+                    + (rng.r#gen::<f32>().mul_add(2.0, -1.0) * 150.0);
+                let attack = pool.spawn(
+                    &mut commands,
+                    EnemyAttack::bundle(
+                        &attack_assets,
+                        my_pos,
+                        velocity,
+                        Ttl::new(rng.r#gen::<f32>().mul_add(3.0, 0.25)),
+                        1,
+                    ),
                 );
-                commands.spawn(EnemyAttack::bundle(
-                    &attack_assets,
-                    my_pos,
-                    velocity,
-                    Ttl::new(random::<f32>().mul_add(3.0, 0.25)),
-                ));
+
+                // Later on, some of every burst homes in on the player.
+                if singleton.level >= Enemy::HOMING_LEVEL && n % Enemy::HOMING_EVERY == 0 {
+                    commands.entity(attack).insert(Homing::ENEMY);
+                }
 
                 commands.spawn(sound_effect(
                     attack_assets
@@ -265,7 +381,10 @@ pub fn handle_enemy_intents(
                     EnemyState::Idling
                 }
             }
-            EnemyIntent::GoToStart(_) => EnemyState::Start,
+            EnemyIntent::GoToStart(_) => {
+                steering.behaviour = SteeringBehaviour::Stop;
+                EnemyState::Start
+            }
         }
     }
 
@@ -277,39 +396,143 @@ pub fn handle_enemy_intents(
 pub struct EnemyStateTimer(Timer);
 
 #[tracing::instrument(skip_all)]
-pub fn tick_enemy_state_timer(mut timers: Query<&mut EnemyStateTimer>, time: Res<Time>) {
-    for mut timer in &mut timers {
-        timer.0.tick(time.delta());
+pub fn tick_enemy_state_timer(
+    mut timers: Query<(&mut EnemyStateTimer, Option<&Slowed>)>,
+    time: Res<Time>,
+) {
+    for (mut timer, slowed) in &mut timers {
+        // Slowed enemies take longer to make up their minds.
+        timer.0.tick(time.delta().mul_f32(Slowed::factor(slowed)));
+    }
+}
+
+/// Staggered enemies give up on their attacks and catch their breath once the stun wears off.
+fn interrupt_stunned_enemies(
+    trigger: Trigger<OnAdd, Stunned>,
+    mut enemies: Query<(
+        &mut Enemy,
+        &mut Steering,
+        &mut LinearVelocity,
+        Has<Knockback>,
+    )>,
+    mut commands: Commands,
+) {
+    let Ok((mut enemy, mut steering, mut velocity, knocked_back)) =
+        enemies.get_mut(trigger.target())
+    else {
+        return;
+    };
+
+    enemy.0 = EnemyState::Idling;
+    steering.behaviour = SteeringBehaviour::Orbit(Enemy::ORBIT_RANGE);
+    // Stop on the spot, unless the hit is sending it flying.
+    if !knocked_back {
+        *velocity = LinearVelocity::ZERO;
+    }
+    commands.entity(trigger.target()).try_remove::<Telegraph>();
+}
+
+/// Picks the animation from what the enemy is doing, unless it's flinching from a hit.
+fn choose_enemy_clip(mut enemies: Query<(&Enemy, &mut SpriteAnimator)>) {
+    for (enemy, mut animator) in &mut enemies {
+        if animator.clip() == Clip::Hurt && !animator.is_finished() {
+            continue;
+        }
+
+        animator.play(match enemy.0 {
+            EnemyState::MovingTo(_) => Clip::Walk,
+            EnemyState::WindingUp(..) | EnemyState::Attacking(_) => Clip::Attack,
+            EnemyState::Start | EnemyState::Idling => Clip::Idle,
+        });
+    }
+}
+
+/// Enemies are briefly invincible after every hit they take.
+fn flinch_when_hit(
+    trigger: Trigger<OnAdd, InvincibilityTimer>,
+    mut animators: Query<&mut SpriteAnimator, With<Enemy>>,
+) {
+    if let Ok(mut animator) = animators.get_mut(trigger.target()) {
+        animator.play(Clip::Hurt);
+    }
+}
+
+/// Which of the enemies it is. They look different and wind up for different lengths of time.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub enum EnemyKind {
+    #[default]
+    Beige,
+    Blue,
+    Green,
+    Yellow,
+}
+
+impl EnemyKind {
+    const ALL: [Self; EnemyAssets::MAX_ASSETS as usize] =
+        [Self::Beige, Self::Blue, Self::Green, Self::Yellow];
+
+    fn animation(self, enemy_assets: &EnemyAssets) -> Handle<SpriteAnimation> {
+        match self {
+            Self::Beige => enemy_assets.beige.clone(),
+            Self::Blue => enemy_assets.blue.clone(),
+            Self::Green => enemy_assets.green.clone(),
+            Self::Yellow => enemy_assets.yellow.clone(),
+        }
+    }
+
+    fn brain(self, enemy_assets: &EnemyAssets) -> Handle<Brain> {
+        match self {
+            Self::Beige => enemy_assets.beige_brain.clone(),
+            Self::Blue => enemy_assets.blue_brain.clone(),
+            Self::Green => enemy_assets.green_brain.clone(),
+            Self::Yellow => enemy_assets.yellow_brain.clone(),
+        }
+    }
+
+    /// How long it telegraphs a volley for, in seconds.
+    pub fn wind_up_secs(self) -> f32 {
+        match self {
+            Self::Beige => 0.6,
+            Self::Blue => 0.45,
+            Self::Green => 0.8,
+            Self::Yellow => 0.35,
+        }
     }
 }
 
 impl Enemy {
     const SPEED: f32 = 300.0;
     const BASE_HEALTH: u32 = 2;
-
-    #[tracing::instrument()]
-    pub fn bundle(enemy_assets: &EnemyAssets, position: Vec2, health: u32) -> impl Bundle {
-        let mut rng = rand::thread_rng();
+    const SCALE: f32 = 0.8;
+    /// How far from the player idle enemies circle.
+    const ORBIT_RANGE: f32 = 350.0;
+    /// The first level where enemies fire homing attacks.
+    const HOMING_LEVEL: u32 = 10;
+    /// Every how many attacks in a burst homes.
+    const HOMING_EVERY: u8 = 3;
+
+    #[tracing::instrument(skip(rng))]
+    pub fn bundle(
+        enemy_assets: &EnemyAssets,
+        rng: &mut impl Rng,
+        position: Vec2,
+        health: u32,
+    ) -> impl Bundle {
         let pick = rng.gen_range(0..=EnemyAssets::MAX_ASSETS);
+        let kind = EnemyKind::ALL[usize::from(pick % EnemyAssets::MAX_ASSETS)];
         (
             StateScoped(Screen::Gameplay),
             Self::default(),
+            kind,
             Health(health),
+            MaxHealth(health),
+            Steering::new(Self::SPEED),
             DisplayHealth::bundle(),
             EnemyStateTimer(Timer::from_seconds(2.0, TimerMode::Repeating)),
-            Transform::from_translation(position.extend(1.0)).with_scale(Vec3::splat(0.8)),
-            Sprite {
-                image: match pick % EnemyAssets::MAX_ASSETS {
-                    0 => enemy_assets.beige.clone(),
-                    1 => enemy_assets.blue.clone(),
-                    2 => enemy_assets.green.clone(),
-                    3 => enemy_assets.yellow.clone(),
-                    _ => {
-                        unreachable!()
-                    }
-                },
-                ..default()
-            },
+            Transform::from_translation(position.extend(1.0)).with_scale(Vec3::splat(Self::SCALE)),
+            SpriteAnimator::new(kind.animation(enemy_assets)),
+            Thinker::new(kind.brain(enemy_assets)),
             RigidBody::Dynamic,
             LinearVelocity::default(),
             Collider::circle(64.0),
@@ -328,32 +551,53 @@ impl Enemy {
 
 #[tracing::instrument(skip_all)]
 pub fn handle_dead_enemies(
-    dead_enemies: Query<Entity, (With<ZeroHealth>, With<Enemy>)>,
+    dead_enemies: Query<(Entity, &Transform, &SpriteAnimator), (With<ZeroHealth>, With<Enemy>)>,
     mut commands: Commands,
     mut singleton: ResMut<ParrylordSingleton>,
+    mut telemetry: EventWriter<TelemetryEvent>,
 ) {
-    for entity in dead_enemies {
+    for (entity, transform, animator) in dead_enemies {
         let Ok(mut entity) = commands.get_entity(entity) else {
             continue;
         };
         entity.try_despawn();
 
+        // The enemy is gone straight away, so it can't be hit or counted any more, and this
+        // plays its death in its place.
+        let mut animator = SpriteAnimator::new(animator.animation.clone());
+        animator.play(Clip::Death);
+        commands
+            .spawn((
+                StateScoped(Screen::Gameplay),
+                Name::new("DyingEnemy"),
+                *transform,
+                animator,
+            ))
+            .observe(
+                |trigger: Trigger<AnimationFinished>, mut commands: Commands| {
+                    commands.entity(trigger.target()).try_despawn();
+                },
+            );
+
         singleton.enemies_killed += 1;
 
+        telemetry.write(TelemetryEvent::EnemyKilled {
+            enemies_killed: singleton.enemies_killed,
+        });
+
         // info!(?singleton);
     }
 }
 
-pub fn get_random_vec2_in_play_area() -> Vec2 {
+pub fn get_random_vec2_in_play_area(rng: &mut impl Rng) -> Vec2 {
     const PLAY_AREA_X: f32 = 600.0;
     const PLAY_AREA_Y: f32 = 200.0;
 
-    let mut thread_rng = rand::thread_rng();
     let x_extents = -PLAY_AREA_X..PLAY_AREA_X;
     let y_extents = -PLAY_AREA_Y..PLAY_AREA_Y;
 
-    let x = thread_rng.gen_range(x_extents);
-    let y = thread_rng.gen_range(y_extents);
+    let x = rng.gen_range(x_extents);
+    let y = rng.gen_range(y_extents);
 
     Vec2::new(x, y)
 }
@@ -1,12 +1,10 @@
 use crate::assets::AttackAssets;
-use crate::parrylord::attack::Attack;
+use crate::parrylord::attack::{power_tier, Attack, PowerTier};
+use crate::parrylord::projectile::ProjectileMotion;
 use crate::parrylord::ttl::Ttl;
 use crate::parrylord::CollisionLayer;
 use crate::screens::Screen;
-use avian2d::prelude::{
-    AngularVelocity, Collider, CollidingEntities, CollisionLayers, LinearVelocity, RigidBody,
-    Sensor,
-};
+use avian2d::prelude::CollisionLayers;
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
@@ -18,33 +16,36 @@ pub fn plugin(app: &mut App) {
 pub struct EnemyAttack;
 
 impl EnemyAttack {
+    /// How much health an attack with `power` takes from the player.
+    pub fn damage(power: u32) -> u32 {
+        power_tier(power) + 1
+    }
+
+    /// Spawn with [`ProjectilePool::spawn`](crate::parrylord::projectile::ProjectilePool::spawn).
     pub fn bundle(
         attack_assets: &AttackAssets,
         pos: Vec2,
-        velocity: LinearVelocity,
+        velocity: Vec2,
         ttl: Ttl,
+        power: u32,
     ) -> impl Bundle {
+        // Only attacks sent back by elites are stronger, so only they get bigger.
+        let size = PowerTier::from_power(power).size;
         (
             StateScoped(Screen::Gameplay),
             Self,
-            Attack(1),
-            Transform::from_xyz(pos.x, pos.y, 3.0).with_scale(Vec3::splat(0.1)),
+            Attack(power),
+            Transform::from_xyz(pos.x, pos.y, 3.0).with_scale(Vec3::splat(0.1 * size)),
             Sprite {
-                image: attack_assets._0.clone(),
                 color: Color::srgb(30.0, 0.1, 0.1),
-                ..default()
+                ..attack_assets.sprite(0)
             },
-            Collider::circle(128.0),
             CollisionLayers::new(
                 [CollisionLayer::EnemyProjectile],
                 [CollisionLayer::PlayerHurt, CollisionLayer::PlayerParry],
             ),
-            RigidBody::Dynamic,
-            Sensor,
-            velocity,
-            AngularVelocity(3.0),
+            ProjectileMotion::new(velocity).with_spin(3.0),
             ttl,
-            CollidingEntities::default(),
         )
     }
 }
@@ -1,18 +1,22 @@
 use crate::screens::Screen;
+use crate::zaphkiel::tween::{Repeat, Tween, TweenTarget};
 use crate::{AppSystems, PausableSystems};
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Health>();
+    app.register_type::<MaxHealth>();
     app.register_type::<ZeroHealth>();
     app.register_type::<InvincibilityTimer>();
     app.register_type::<DisplayHealth>();
 
+    app.add_observer(blink_while_invincible);
+    app.add_observer(stop_blinking);
+
     app.add_systems(
         Update,
         (
             tick_invincibility_timer.in_set(AppSystems::TickTimers),
-            change_invinsibile_visibility,
             despawn_done_invincibility_timers,
             check_health.pipe(handle_health),
             display_health,
@@ -26,6 +30,11 @@ pub fn plugin(app: &mut App) {
 #[reflect(Component)]
 pub struct Health(pub u32);
 
+/// The [`Health`] something started with.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct MaxHealth(pub u32);
+
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
 pub struct ZeroHealth;
@@ -85,26 +94,52 @@ pub fn tick_invincibility_timer(mut timers: Query<&mut InvincibilityTimer>, time
 
 const CHANGE_TIME_THING: f32 = 0.5;
 
-pub fn change_invinsibile_visibility(mut query: Query<(&mut Visibility, &InvincibilityTimer)>) {
-    for (mut vis, timer) in &mut query {
-        let time_remaining = timer.0.remaining_secs();
-        let fract = (time_remaining % CHANGE_TIME_THING) / CHANGE_TIME_THING;
+/// Blinks, unless something else is already tweening, like a fade.
+fn blink_while_invincible(
+    trigger: Trigger<OnAdd, InvincibilityTimer>,
+    tweens: Query<(), With<Tween>>,
+    mut commands: Commands,
+) {
+    if tweens.contains(trigger.target()) {
+        return;
+    }
 
-        if fract > 0.3 {
-            *vis = Visibility::Hidden;
-        } else {
-            *vis = Visibility::Inherited;
-        }
+    commands.entity(trigger.target()).try_insert(
+        Tween::new(
+            TweenTarget::Blink {
+                visible_fraction: 0.3,
+            },
+            CHANGE_TIME_THING,
+        )
+        .with_repeat(Repeat::Forever),
+    );
+}
+
+fn stop_blinking(
+    trigger: Trigger<OnRemove, InvincibilityTimer>,
+    mut visibilities: Query<&mut Visibility>,
+    tweens: Query<&Tween>,
+    mut commands: Commands,
+) {
+    if let Ok(mut visibility) = visibilities.get_mut(trigger.target()) {
+        *visibility = Visibility::Inherited;
+    }
+
+    // Leave any other tween that's taken over since, like a fade, alone.
+    if tweens
+        .get(trigger.target())
+        .is_ok_and(|it| matches!(it.target, TweenTarget::Blink { .. }))
+    {
+        commands.entity(trigger.target()).try_remove::<Tween>();
     }
 }
 
 pub fn despawn_done_invincibility_timers(
-    mut timers: Query<(&InvincibilityTimer, Entity, &mut Visibility)>,
+    timers: Query<(&InvincibilityTimer, Entity)>,
     mut commands: Commands,
 ) {
-    for (timer, entity, mut vis) in &mut timers {
+    for (timer, entity) in &timers {
         if timer.0.just_finished() {
-            *vis = Visibility::Inherited;
             let Ok(mut entity) = commands.get_entity(entity) else {
                 continue;
             };
@@ -0,0 +1,149 @@
+//! Attacks that steer towards a target.
+//!
+//! [`Homing`] turns an attack's [`ProjectileMotion`] towards the closest target in a cone in front
+//! of it, no faster than its turn rate, so it curves instead of snapping.
+
+use crate::parrylord::enemy::Enemy;
+use crate::parrylord::player::Player;
+use crate::parrylord::projectile::{move_projectiles, ProjectileMotion};
+use crate::parrylord::spawning::Spawning;
+use crate::screens::Screen;
+use crate::{AppSystems, PausableSystems};
+use bevy::prelude::*;
+use std::f32::consts::FRAC_PI_4;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Homing>();
+    app.register_type::<ParryHoming>();
+    app.init_resource::<ParryHoming>();
+
+    app.add_systems(
+        Update,
+        steer_homing_attacks
+            .after(AppSystems::RecordInput)
+            .before(move_projectiles)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+pub enum HomingTarget {
+    Enemies,
+    Player,
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Homing {
+    pub target: HomingTarget,
+    /// How fast the attack can turn, in radians per second.
+    pub turn_rate: f32,
+    /// How far off the attack's heading targets can be, in radians either way.
+    pub cone: f32,
+    /// How far away targets can be.
+    pub range: f32,
+}
+
+impl Homing {
+    /// Mild homing for enemy attacks, which the player can still sidestep.
+    pub const ENEMY: Self = Self {
+        target: HomingTarget::Player,
+        turn_rate: 0.8,
+        cone: FRAC_PI_4,
+        range: 600.0,
+    };
+
+    /// The angle to turn `velocity` by to face the closest of `targets` in range and in the cone,
+    /// or zero if there isn't one.
+    fn turn_towards<'a>(
+        &self,
+        position: Vec2,
+        velocity: Vec2,
+        targets: impl IntoIterator<Item = &'a GlobalTransform>,
+    ) -> f32 {
+        if velocity == Vec2::ZERO {
+            return 0.0;
+        }
+
+        targets
+            .into_iter()
+            .map(|it| it.translation().truncate() - position)
+            .filter(|offset| offset.length_squared() <= self.range * self.range)
+            .map(|offset| (offset.length_squared(), velocity.angle_to(offset)))
+            .filter(|&(_, angle)| angle.abs() <= self.cone)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map_or(0.0, |(_, angle)| angle)
+    }
+}
+
+/// How parried attacks home in on enemies. Stronger parries steer harder.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct ParryHoming {
+    /// The turn rate of a single parried attack, in radians per second. Zero turns homing off.
+    pub turn_rate: f32,
+    /// Added to the turn rate for every other attack parried at the same time.
+    pub turn_rate_per_parry: f32,
+    pub max_turn_rate: f32,
+    pub cone: f32,
+    pub range: f32,
+}
+
+impl Default for ParryHoming {
+    fn default() -> Self {
+        Self {
+            turn_rate: 1.0,
+            turn_rate_per_parry: 0.5,
+            max_turn_rate: 4.0,
+            cone: FRAC_PI_4,
+            range: 800.0,
+        }
+    }
+}
+
+impl ParryHoming {
+    /// Homing for an attack made by parrying `parried` attacks at once, if homing is on.
+    pub fn for_parry(&self, parried: u32) -> Option<Homing> {
+        if self.turn_rate <= 0.0 {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let extra = parried.saturating_sub(1) as f32;
+        Some(Homing {
+            target: HomingTarget::Enemies,
+            turn_rate: self
+                .turn_rate_per_parry
+                .mul_add(extra, self.turn_rate)
+                .min(self.max_turn_rate),
+            cone: self.cone,
+            range: self.range,
+        })
+    }
+}
+
+fn steer_homing_attacks(
+    time: Res<Time>,
+    mut attacks: Query<(&Homing, &mut ProjectileMotion, &Transform)>,
+    // Nothing to hit until they're through their portal.
+    enemies: Query<&GlobalTransform, (With<Enemy>, Without<Spawning>)>,
+    player: Query<&GlobalTransform, With<Player>>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    for (homing, mut motion, transform) in &mut attacks {
+        let position = transform.translation.truncate();
+
+        let turn = match homing.target {
+            HomingTarget::Enemies => homing.turn_towards(position, motion.velocity, enemies.iter()),
+            HomingTarget::Player => homing.turn_towards(position, motion.velocity, player.iter()),
+        };
+
+        // Don't overshoot the target within a frame.
+        motion.turn_rate = (turn / delta).clamp(-homing.turn_rate, homing.turn_rate);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::parrylord::replay::ReplayPlayback;
+use crate::screens::Screen;
+use crate::{AppSystems, PausableSystems};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<PlayerInput>();
+    app.init_resource::<PlayerInput>();
+
+    app.add_systems(
+        Update,
+        read_player_input
+            .run_if(not(resource_exists::<ReplayPlayback>))
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems),
+    );
+}
+
+/// What the player is doing this frame.
+///
+/// Gameplay systems read this instead of the keyboard, mouse and window directly,
+/// so that a run can be recorded and played back.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PlayerInput {
+    /// The direction the player wants to move in, not normalized.
+    pub movement: Vec2,
+    /// The cursor position in world space, if the cursor is inside the window.
+    pub cursor: Option<Vec2>,
+    /// Whether the player tried to parry this frame.
+    pub parry: bool,
+}
+
+/// Fills [`PlayerInput`] from the keyboard and mouse.
+pub fn read_player_input(
+    mut input: ResMut<PlayerInput>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+) {
+    let left = keyboard_input.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]);
+    let right = keyboard_input.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]);
+    let up = keyboard_input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]);
+    let down = keyboard_input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]);
+
+    let horizontal = f32::from(i8::from(right) - i8::from(left));
+    let vertical = f32::from(i8::from(up) - i8::from(down));
+
+    let cursor = window
+        .single()
+        .ok()
+        .and_then(Window::cursor_position)
+        .and_then(|mouse| {
+            let (camera, camera_transform) = camera.single().ok()?;
+            camera.viewport_to_world_2d(camera_transform, mouse).ok()
+        });
+
+    *input = PlayerInput {
+        movement: Vec2::new(horizontal, vertical),
+        cursor,
+        parry: mouse_input.just_pressed(MouseButton::Left),
+    };
+}
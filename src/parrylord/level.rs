@@ -1,11 +1,15 @@
 use crate::assets::{LevelAssets, PlayerAssets};
 use crate::parrylord::enemy::{Enemy, SpawnEnemy};
+use crate::parrylord::mode::GameMode;
 use crate::parrylord::player::Player;
+use crate::parrylord::rng::{FixedSeed, GameRng, RunSeed};
 use crate::parrylord::CollisionLayer;
 use crate::screens::Screen;
-use crate::{ParrylordSingleton, PausableSystems};
+use crate::telemetry::TelemetryEvent;
+use crate::{AppSystems, ParrylordSingleton, PausableSystems};
 use avian2d::prelude::{Collider, CollisionLayers, RigidBody};
 use bevy::prelude::*;
+use std::time::Duration;
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Level>();
@@ -13,10 +17,14 @@ pub fn plugin(app: &mut App) {
     app.register_type::<LevelBackground>();
     app.register_type::<Walls>();
     app.register_type::<Wall>();
+    app.register_type::<StartingLevel>();
+    app.init_resource::<StartingLevel>();
+    app.register_type::<RunDuration>();
+    app.init_resource::<RunDuration>();
 
     app.add_systems(
         Update,
-        new_level
+        (tick_run_duration.in_set(AppSystems::TickTimers), new_level)
             .run_if(in_state(Screen::Gameplay))
             .in_set(PausableSystems),
     );
@@ -26,8 +34,32 @@ pub fn plugin(app: &mut App) {
 #[reflect(Component)]
 pub struct Level;
 
+/// The level every run starts at.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct StartingLevel(pub u32);
+
+impl Default for StartingLevel {
+    fn default() -> Self {
+        Self(ParrylordSingleton::default().level)
+    }
+}
+
+/// How long the current (or last) run has been played for, not counting pauses.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct RunDuration(pub Duration);
+
+fn tick_run_duration(mut run_duration: ResMut<RunDuration>, time: Res<Time>) {
+    run_duration.0 += time.delta();
+}
+
 impl Level {
-    pub fn bundle(level_assets: &LevelAssets, player_assets: &PlayerAssets) -> impl Bundle {
+    pub fn bundle(
+        level_assets: &LevelAssets,
+        player_assets: &PlayerAssets,
+        mode: GameMode,
+    ) -> impl Bundle {
         (
             StateScoped(Screen::Gameplay),
             Name::new("Level"),
@@ -35,7 +67,7 @@ impl Level {
             Transform::default(),
             Visibility::default(),
             children![
-                Player::bundle(player_assets),
+                Player::bundle(player_assets, mode.player_health()),
                 LevelBackground::bundle(level_assets),
             ],
         )
@@ -48,15 +80,32 @@ pub fn spawn_level(
     level_assets: Res<LevelAssets>,
     player_assets: Res<PlayerAssets>,
     mut parrylord_singleton: ResMut<ParrylordSingleton>,
+    mode: Res<GameMode>,
+    starting_level: Res<StartingLevel>,
+    fixed_seed: Res<FixedSeed>,
+    mut run_seed: ResMut<RunSeed>,
+    mut rng: ResMut<GameRng>,
+    mut run_duration: ResMut<RunDuration>,
+    mut telemetry: EventWriter<TelemetryEvent>,
 ) {
-    commands.spawn(Level::bundle(&level_assets, &player_assets));
-    *parrylord_singleton = ParrylordSingleton::default();
+    commands.spawn(Level::bundle(&level_assets, &player_assets, *mode));
+    *parrylord_singleton = ParrylordSingleton {
+        level: starting_level.0.max(1),
+        ..default()
+    };
+    *run_duration = RunDuration::default();
+
+    let seed = fixed_seed.0.unwrap_or_else(rand::random);
+    *run_seed = RunSeed(seed);
+    *rng = GameRng::from_seed(seed);
+    telemetry.write(TelemetryEvent::RunStarted { seed });
 }
 
 fn new_level(
     enemies: Query<(), With<Enemy>>,
     mut spawn_enemy_event_writer: EventWriter<SpawnEnemy>,
     mut singleton: ResMut<ParrylordSingleton>,
+    mut telemetry: EventWriter<TelemetryEvent>,
 ) {
     if !enemies.is_empty() {
         return;
@@ -64,11 +113,17 @@ fn new_level(
 
     // info!(?singleton);
 
-    for _ in 0..singleton.level {
+    let enemies = singleton.level;
+    for _ in 0..enemies {
         spawn_enemy_event_writer.write(SpawnEnemy);
     }
 
     singleton.level += 1;
+
+    telemetry.write(TelemetryEvent::LevelStarted {
+        level: singleton.level,
+        enemies,
+    });
 }
 
 //     let root = context.entity;
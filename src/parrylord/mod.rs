@@ -2,28 +2,66 @@ use crate::assets;
 use avian2d::prelude::PhysicsLayer;
 use bevy::prelude::*;
 
+pub mod ai;
+pub mod animation;
 mod attack;
+#[cfg(not(target_family = "wasm"))]
+pub mod benchmark;
+pub mod clash;
+pub mod deflect;
 pub mod dynamic_character_2d;
 pub mod enemy;
 pub mod enemy_attack;
 pub mod health;
+pub mod homing;
+pub mod input;
 pub mod level;
+pub mod mode;
+pub mod navigation;
 pub mod player;
 pub mod player_attack;
+pub mod projectile;
+pub mod replay;
+pub mod rng;
+pub mod spawning;
+pub mod status;
+pub mod steering;
+pub mod telegraph;
 pub mod ttl;
 
 pub fn plugin(app: &mut App) {
     app.add_plugins((
+        // Before `assets`, which loads animations and brains.
+        (animation::plugin, ai::plugin),
         assets::plugin,
-        attack::plugin,
+        (
+            attack::plugin,
+            player_attack::plugin,
+            enemy_attack::plugin,
+            projectile::plugin,
+            clash::plugin,
+            homing::plugin,
+            deflect::plugin,
+        ),
         player::plugin,
         level::plugin,
         dynamic_character_2d::plugin,
-        enemy::plugin,
+        (
+            enemy::plugin,
+            telegraph::plugin,
+            spawning::plugin,
+            navigation::plugin,
+            steering::plugin,
+        ),
         health::plugin,
-        player_attack::plugin,
-        enemy_attack::plugin,
+        status::plugin,
         ttl::plugin,
+        rng::plugin,
+        mode::plugin,
+        input::plugin,
+        replay::plugin,
+        #[cfg(not(target_family = "wasm"))]
+        benchmark::plugin,
     ));
 }
 
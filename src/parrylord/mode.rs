@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<GameMode>();
+    app.init_resource::<GameMode>();
+}
+
+/// The rule set the next run is played with.
+#[derive(
+    Resource, Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect, Serialize, Deserialize,
+)]
+#[cfg_attr(not(target_family = "wasm"), derive(clap::ValueEnum))]
+#[reflect(Resource)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    #[default]
+    Normal,
+    /// The player goes down in a single hit.
+    Hardcore,
+}
+
+impl GameMode {
+    pub const fn player_health(self) -> u32 {
+        match self {
+            Self::Normal => 20,
+            Self::Hardcore => 1,
+        }
+    }
+}
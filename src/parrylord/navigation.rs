@@ -0,0 +1,232 @@
+//! A grid over the arena for finding paths around walls.
+//!
+//! Cells too close to a [`Wall`] for an enemy to fit are blocked. The grid is rebuilt whenever
+//! walls move, are added, or their colliders change, so arenas with obstacles inside just work.
+
+use crate::parrylord::level::Wall;
+use crate::screens::Screen;
+use avian2d::prelude::ColliderAabb;
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<NavGrid>();
+    app.init_resource::<NavGrid>();
+
+    app.add_systems(Update, rebuild_nav_grid.run_if(in_state(Screen::Gameplay)));
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct NavGrid {
+    /// Width and height in cells.
+    size: UVec2,
+    blocked: Vec<bool>,
+}
+
+impl Default for NavGrid {
+    fn default() -> Self {
+        let size = (Self::ARENA_SIZE / Self::CELL_SIZE).ceil().as_uvec2();
+        Self {
+            size,
+            blocked: vec![false; (size.x * size.y) as usize],
+        }
+    }
+}
+
+impl NavGrid {
+    const CELL_SIZE: f32 = 48.0;
+    const ARENA_SIZE: Vec2 = Vec2::new(1920.0, 1080.0);
+    /// How far from walls enemies have to stay, which is about how big they are.
+    const AGENT_RADIUS: f32 = 52.0;
+    /// The cost of moving to a neighbouring cell, straight and diagonally.
+    const STRAIGHT_COST: u32 = 10;
+    const DIAGONAL_COST: u32 = 14;
+
+    fn origin() -> Vec2 {
+        -Self::ARENA_SIZE / 2.0
+    }
+
+    fn cell(&self, position: Vec2) -> Option<UVec2> {
+        let cell = ((position - Self::origin()) / Self::CELL_SIZE)
+            .floor()
+            .as_ivec2();
+        (cell.cmpge(IVec2::ZERO).all() && cell.cmplt(self.size.as_ivec2()).all())
+            .then(|| cell.as_uvec2())
+    }
+
+    fn center(cell: UVec2) -> Vec2 {
+        Self::origin() + (cell.as_vec2() + 0.5) * Self::CELL_SIZE
+    }
+
+    fn index(&self, cell: UVec2) -> usize {
+        (cell.y * self.size.x + cell.x) as usize
+    }
+
+    fn is_cell_blocked(&self, cell: UVec2) -> bool {
+        self.blocked[self.index(cell)]
+    }
+
+    /// Whether an enemy can't be at `position`. Everything outside the arena is blocked.
+    pub fn is_blocked(&self, position: Vec2) -> bool {
+        self.cell(position)
+            .is_none_or(|cell| self.is_cell_blocked(cell))
+    }
+
+    /// Whether an enemy can go straight from `start` to `end` without running into a wall.
+    pub fn line_of_sight(&self, start: Vec2, end: Vec2) -> bool {
+        let steps = (start.distance(end) / (Self::CELL_SIZE / 2.0))
+            .ceil()
+            .max(1.0);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let steps = steps as u32;
+
+        (1..=steps).all(|step| {
+            #[allow(clippy::cast_precision_loss)]
+            let t = step as f32 / steps as f32;
+            !self.is_blocked(start.lerp(end, t))
+        })
+    }
+
+    /// The corners to go through to get from `start` to `goal`, ending with `goal`, or `None` if
+    /// there's no way through.
+    pub fn find_path(&self, start: Vec2, goal: Vec2) -> Option<Vec<Vec2>> {
+        if self.line_of_sight(start, goal) {
+            return Some(vec![goal]);
+        }
+
+        let start_cell = self.cell(start)?;
+        let goal_cell = self.cell(goal)?;
+        if self.is_cell_blocked(goal_cell) {
+            return None;
+        }
+
+        let heuristic = |cell: UVec2| {
+            let delta = (cell.as_ivec2() - goal_cell.as_ivec2()).abs().as_uvec2();
+            let (long, short) = (delta.max_element(), delta.min_element());
+            Self::STRAIGHT_COST * (long - short) + Self::DIAGONAL_COST * short
+        };
+
+        let len = self.blocked.len();
+        let mut costs = vec![u32::MAX; len];
+        let mut came_from = vec![None; len];
+        let mut open = BinaryHeap::new();
+
+        costs[self.index(start_cell)] = 0;
+        open.push(Reverse((heuristic(start_cell), start_cell.to_array())));
+
+        while let Some(Reverse((_, cell))) = open.pop() {
+            let cell = UVec2::from_array(cell);
+            if cell == goal_cell {
+                break;
+            }
+            let cost = costs[self.index(cell)];
+
+            for neighbour in self.neighbours(cell) {
+                let diagonal = neighbour.x != cell.x && neighbour.y != cell.y;
+                let step = if diagonal {
+                    Self::DIAGONAL_COST
+                } else {
+                    Self::STRAIGHT_COST
+                };
+
+                let index = self.index(neighbour);
+                if cost + step < costs[index] {
+                    costs[index] = cost + step;
+                    came_from[index] = Some(cell);
+                    open.push(Reverse((
+                        cost + step + heuristic(neighbour),
+                        neighbour.to_array(),
+                    )));
+                }
+            }
+        }
+
+        let mut cells = vec![goal_cell];
+        while let Some(previous) = came_from[self.index(*cells.last()?)] {
+            cells.push(previous);
+        }
+        if *cells.last()? != start_cell {
+            return None;
+        }
+        cells.reverse();
+
+        Some(self.smooth(start, goal, &cells))
+    }
+
+    /// Walkable cells next to `cell`, not cutting corners past blocked ones.
+    fn neighbours(&self, cell: UVec2) -> impl Iterator<Item = UVec2> + '_ {
+        let open = move |x: i32, y: i32| {
+            let neighbour = cell.as_ivec2() + IVec2::new(x, y);
+            (neighbour.cmpge(IVec2::ZERO).all() && neighbour.cmplt(self.size.as_ivec2()).all())
+                .then(|| neighbour.as_uvec2())
+                .filter(|&it| !self.is_cell_blocked(it))
+        };
+
+        (-1..=1)
+            .flat_map(|y| (-1..=1).map(move |x| (x, y)))
+            .filter(|&offset| offset != (0, 0))
+            .filter(move |&(x, y)| {
+                x == 0 || y == 0 || (open(x, 0).is_some() && open(0, y).is_some())
+            })
+            .filter_map(move |(x, y)| open(x, y))
+    }
+
+    /// Skips every cell that can be seen past, so paths don't zigzag along the grid.
+    fn smooth(&self, start: Vec2, goal: Vec2, cells: &[UVec2]) -> Vec<Vec2> {
+        // Without the start cell, which we're already in, and the goal cell, which the goal is.
+        let mut points = cells
+            .get(1..cells.len() - 1)
+            .unwrap_or_default()
+            .iter()
+            .map(|&cell| Self::center(cell))
+            .collect::<Vec<_>>();
+        points.push(goal);
+
+        let mut waypoints = Vec::new();
+        let mut from = start;
+        let mut next = 0;
+        while next < points.len() {
+            // The furthest point in sight, or at least the next one.
+            let furthest = (next..points.len())
+                .rev()
+                .find(|&it| self.line_of_sight(from, points[it]))
+                .unwrap_or(next);
+            from = points[furthest];
+            waypoints.push(from);
+            next = furthest + 1;
+        }
+
+        waypoints
+    }
+}
+
+fn rebuild_nav_grid(
+    walls: Query<&ColliderAabb, With<Wall>>,
+    changed: Query<(), (With<Wall>, Changed<ColliderAabb>)>,
+    mut removed: RemovedComponents<Wall>,
+    mut grid: ResMut<NavGrid>,
+) {
+    let removed = removed.read().count() > 0;
+    if changed.is_empty() && !removed {
+        return;
+    }
+
+    let mut new = NavGrid::default();
+    for y in 0..new.size.y {
+        for x in 0..new.size.x {
+            let cell = UVec2::new(x, y);
+            let center = NavGrid::center(cell);
+            let blocked = walls.iter().any(|aabb| {
+                let closest = center.clamp(aabb.min, aabb.max);
+                closest.distance(center) < NavGrid::AGENT_RADIUS
+            });
+
+            let index = new.index(cell);
+            new.blocked[index] = blocked;
+        }
+    }
+
+    *grid = new;
+}
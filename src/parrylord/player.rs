@@ -1,35 +1,32 @@
 use crate::assets::PlayerAssets;
 use crate::audio::sound_effect;
+use crate::parrylord::animation::{animate_sprites, Clip, SpriteAnimator};
+use crate::parrylord::attack::Attack;
 use crate::parrylord::dynamic_character_2d::CharacterControllerBundle;
+use crate::parrylord::enemy_attack::EnemyAttack;
 use crate::parrylord::health::{DisplayHealth, Health, InvincibilityTimer, ZeroHealth};
 use crate::parrylord::player_attack::PlayerAttackIndicator;
+use crate::parrylord::projectile::{ProjectileHits, ReleaseProjectile};
 use crate::parrylord::CollisionLayer;
 use crate::screens::Screen;
+use crate::telemetry::{DamageTarget, TelemetryEvent};
 use crate::{AppSystems, PausableSystems};
-use avian2d::parry::na::RealField;
 use avian2d::prelude::{Collider, CollidingEntities, CollisionLayers, LinearVelocity, Sensor};
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use rand::prelude::SliceRandom;
-use std::fmt::Debug;
 
 pub fn plugin(app: &mut App) {
     app.register_type::<Player>();
     app.register_type::<PlayerHurtBox>();
-    app.register_type::<PlayerSprite>();
-    app.register_type::<AnimationTimer>();
 
     app.add_systems(
         Update,
-        (hurt, handle_player_death, walk_animation, animate_sprite)
-            .run_if(in_state(Screen::Gameplay))
-            .in_set(PausableSystems),
-    );
-
-    app.add_systems(
-        Update,
-        tick_animation_timer
-            .in_set(AppSystems::TickTimers)
+        (
+            hurt.in_set(AppSystems::Update),
+            handle_player_death,
+            choose_player_clip.before(animate_sprites),
+        )
             .run_if(in_state(Screen::Gameplay))
             .in_set(PausableSystems),
     );
@@ -43,167 +40,51 @@ pub struct Player;
 #[reflect(Component)]
 pub struct PlayerHurtBox;
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
-#[reflect(Component)]
-pub enum PlayerSprite {
-    Front(u8),
-    Walk(f32, u8),
-    Stand(f32),
-}
-
-impl Default for PlayerSprite {
-    fn default() -> Self {
-        Self::Front(0)
-    }
-}
-
-impl PlayerSprite {
-    #[must_use]
-    #[tracing::instrument()]
-    fn advance_frame(self) -> Self {
-        match self {
-            Self::Front(n) => Self::Front((n + 1) % 3),
-            Self::Walk(d, n) => Self::Walk(d, (n + 1) % 2),
-            Self::Stand(_) => self,
-        }
-    }
-
-    #[tracing::instrument()]
-    fn flip_x(&self) -> bool {
-        match self {
-            Self::Front(_) => false,
-            Self::Walk(dir, _) | Self::Stand(dir) => !dir.is_sign_positive(),
-        }
-    }
-
-    #[tracing::instrument()]
-    fn get_sprite(&self, player_assets: &PlayerAssets) -> Handle<Image> {
-        let PlayerAssets {
-            front1,
-            front2,
-            walk1,
-            walk2,
-            stand,
-            attack_indicator: _,
-            hurt_sfx: _,
-        } = player_assets.clone();
-
-        match self {
-            Self::Front(0) => front1,
-            Self::Front(1) => front2,
-            Self::Walk(_, 0) => walk1,
-            Self::Walk(_, 1) => walk2,
-            Self::Stand(_) => stand,
-
-            what => unreachable!("get_sprite: {what:?}"),
-        }
-    }
-
-    #[tracing::instrument()]
-    fn max_frames(&self) -> u8 {
-        match self {
-            PlayerSprite::Front(_) => 2,
-            PlayerSprite::Walk(_, _) => 2,
-            PlayerSprite::Stand(_) => 1,
-        }
-    }
-}
-
-#[tracing::instrument(skip_all)]
-pub fn walk_animation(player: Single<(&mut PlayerSprite, &LinearVelocity), With<Player>>) {
+/// Picks the animation from how the player is moving, unless they're hurt.
+fn choose_player_clip(
+    player: Single<(&mut SpriteAnimator, &mut Sprite, &LinearVelocity), With<Player>>,
+) {
     const MINIMUM_SPEED: f32 = 1.0;
     const MINIMUM_X_SPEED: f32 = 20.0;
 
-    let (mut player_sprite, velocity) = player.into_inner();
-
-    let frame_number = match *player_sprite {
-        PlayerSprite::Front(n) | PlayerSprite::Walk(_, n) => n,
-        PlayerSprite::Stand(_) => 0,
-    };
-
-    let front = velocity.length_squared() < MINIMUM_SPEED * MINIMUM_SPEED;
-    if front {
-        *player_sprite = PlayerSprite::Front(frame_number % PlayerSprite::Front(0).max_frames());
-    } else {
-        let run = velocity.length_squared() > MINIMUM_X_SPEED * MINIMUM_X_SPEED;
-        let facing_right = velocity.x.is_sign_positive();
-
-        *player_sprite = match (run, facing_right) {
-            (false, false) => PlayerSprite::Stand(-1.0),
-            (false, true) => PlayerSprite::Stand(1.0),
-            (true, false) => {
-                PlayerSprite::Walk(-1.0, frame_number % PlayerSprite::Walk(0.0, 0).max_frames())
-            }
-            (true, true) => {
-                PlayerSprite::Walk(1.0, frame_number % PlayerSprite::Walk(0.0, 0).max_frames())
-            }
-        }
-    }
-}
+    let (mut animator, mut sprite, velocity) = player.into_inner();
 
-#[tracing::instrument(skip_all)]
-fn animate_sprite(
-    player: Single<(&mut Sprite, &AnimationTimer, &mut PlayerSprite), With<Player>>,
-    player_assets: Res<PlayerAssets>,
-) {
-    let (mut sprite, timer, mut sprite_state) = player.into_inner();
-
-    let player_assets = &*player_assets;
-
-    sprite.flip_x = sprite_state.flip_x();
-    sprite.image = sprite_state.get_sprite(player_assets);
-
-    if !timer.0.just_finished() {
+    if animator.clip() == Clip::Hurt && !animator.is_finished() {
         return;
     }
 
-    *sprite_state = sprite_state.advance_frame();
-}
-
-#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
-#[reflect(Component)]
-pub struct AnimationTimer(Timer);
+    let speed_squared = velocity.length_squared();
+    let clip = if speed_squared < MINIMUM_SPEED * MINIMUM_SPEED {
+        Clip::Idle
+    } else if speed_squared > MINIMUM_X_SPEED * MINIMUM_X_SPEED {
+        Clip::Walk
+    } else {
+        Clip::Stand
+    };
 
-impl AnimationTimer {
-    #[tracing::instrument()]
-    fn from_seconds(seconds: f32) -> impl Bundle {
-        (Self(Timer::from_seconds(seconds, TimerMode::Repeating)),)
-    }
-}
-#[tracing::instrument(skip_all)]
-fn tick_animation_timer(mut timers: Query<&mut AnimationTimer>, time: Res<Time>) {
-    for mut timer in &mut timers {
-        timer.0.tick(time.delta());
+    // The idle frames face the camera.
+    let flip_x = clip != Clip::Idle && velocity.x.is_sign_negative();
+    if sprite.flip_x != flip_x {
+        sprite.flip_x = flip_x;
     }
+
+    animator.play(clip);
 }
 
 impl Player {
     #[tracing::instrument()]
-    pub fn bundle(player_assets: &PlayerAssets) -> impl Bundle {
-        // A texture atlas is a way to split a single image into a grid of related images.
-        // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
-        // let layout = TextureAtlasLayout::from_grid(UVec2::splat(32), 6, 2, Some(UVec2::splat(1)), None);
-        // let texture_atlas_layout = texture_atlas_layouts.add(layout);
-        // let player_animation = PlayerAnimation::new();
-
+    pub fn bundle(player_assets: &PlayerAssets, health: u32) -> impl Bundle {
         (
             Name::new("Player"),
-            Health(20),
+            Health(health),
             DisplayHealth::bundle(),
             Self,
             CharacterControllerBundle::new(Collider::capsule(48.0, 48.0)),
             Sprite {
-                image: player_assets.front1.clone(),
-                // color: Color::srgb(3.0, 3.0, 3.0),
-                // texture_atlas: Some(TextureAtlas {
-                //     layout: texture_atlas_layout,
-                //     index: player_animation.get_atlas_index(),
-                // }),
                 anchor: Anchor::Center,
                 ..default()
             },
-            PlayerSprite::default(),
-            AnimationTimer::from_seconds(5.0 / 60.0),
+            SpriteAnimator::new(player_assets.animation.clone()),
             Transform::from_scale(Vec2::splat(0.5).extend(1.0)),
             children![
                 PlayerAttackIndicator::bundle(player_assets),
@@ -225,20 +106,45 @@ impl Player {
 
 #[tracing::instrument(skip_all)]
 fn hurt(
-    collisions_with_hurt_box: Single<&CollidingEntities, With<PlayerHurtBox>>,
-    health: Single<(&mut Health, Entity), (With<Player>, Without<InvincibilityTimer>)>,
+    hurt_box: Single<(Entity, &CollidingEntities), With<PlayerHurtBox>>,
+    enemy_attacks: Query<(Entity, &ProjectileHits, &Attack), With<EnemyAttack>>,
+    health: Single<
+        (&mut Health, &mut SpriteAnimator, Entity),
+        (With<Player>, Without<InvincibilityTimer>),
+    >,
     player_assets: Res<PlayerAssets>,
     mut commands: Commands,
+    mut telemetry: EventWriter<TelemetryEvent>,
 ) {
-    let collisions = *collisions_with_hurt_box;
-    let (mut health, entity) = health.into_inner();
+    let (hurt_box, touching_enemies) = *hurt_box;
+    let (mut health, mut animator, entity) = health.into_inner();
 
-    if collisions.is_empty() {
+    let attacks = enemy_attacks
+        .iter()
+        .filter(|(_, hits, _)| hits.contains(&hurt_box))
+        .map(|(attack, _, power)| (attack, power.0))
+        .collect::<Vec<_>>();
+
+    if touching_enemies.is_empty() && attacks.is_empty() {
         return;
     }
 
+    // Only the strongest attack counts, since the player is invincible right after.
+    let amount = attacks
+        .iter()
+        .map(|&(_, power)| EnemyAttack::damage(power))
+        .max()
+        .unwrap_or(1);
+
     // log!(Level::Info, "Health: {health:?}");
-    health.0 -= 1;
+    health.0 = health.0.saturating_sub(amount);
+    animator.play(Clip::Hurt);
+
+    telemetry.write(TelemetryEvent::Damage {
+        target: DamageTarget::Player,
+        amount,
+        remaining_health: health.0,
+    });
 
     commands
         .entity(entity)
@@ -247,14 +153,18 @@ fn hurt(
             TimerMode::Once,
         )));
 
-    for &attack_entity in &collisions.0 {
-        let Ok(mut entity) = commands.get_entity(attack_entity) else {
+    for &enemy in touching_enemies.iter() {
+        let Ok(mut entity) = commands.get_entity(enemy) else {
             continue;
         };
 
         entity.try_despawn();
     }
 
+    for (attack, _) in attacks {
+        commands.trigger_targets(ReleaseProjectile, attack);
+    }
+
     commands.spawn(sound_effect(
         player_assets
             .hurt_sfx
@@ -268,10 +178,12 @@ fn hurt(
 fn handle_player_death(
     query: Option<Single<(), (With<Player>, With<ZeroHealth>)>>,
     mut next_screen: ResMut<NextState<Screen>>,
+    mut telemetry: EventWriter<TelemetryEvent>,
 ) {
     if query.is_none() {
         return;
     }
 
+    telemetry.write(TelemetryEvent::PlayerDied);
     next_screen.set(Screen::HighScore);
 }
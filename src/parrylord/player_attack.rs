@@ -1,18 +1,29 @@
 use crate::assets::{AttackAssets, PlayerAssets};
 use crate::audio::sound_effect;
-use crate::parrylord::attack::Attack;
+use crate::parrylord::attack::{Attack, AttackPayload, PowerTier};
+use crate::parrylord::clash::Clashes;
+use crate::parrylord::deflect::{Rally, RallyHit};
 use crate::parrylord::enemy_attack::EnemyAttack;
 use crate::parrylord::health::{Health, InvincibilityTimer};
+use crate::parrylord::homing::{Homing, ParryHoming};
+use crate::parrylord::input::PlayerInput;
 use crate::parrylord::level::Wall;
 use crate::parrylord::player::Player;
+use crate::parrylord::projectile::{
+    FadingProjectile, Projectile, ProjectileConfig, ProjectileHits, ProjectileMotion,
+    ProjectilePool, ReleaseProjectile,
+};
+use crate::parrylord::rng::GameRng;
+use crate::parrylord::status::ApplyPayload;
 use crate::parrylord::ttl::Ttl;
 use crate::parrylord::CollisionLayer;
 use crate::screens::Screen;
-use crate::{exponential_decay, AppSystems, ParrylordSingleton, PausableSystems};
-use avian2d::prelude::{
-    AngularVelocity, Collider, CollidingEntities, CollisionLayers, LinearVelocity, RigidBody,
-    Sensor,
-};
+use crate::telemetry::{DamageTarget, TelemetryEvent};
+use crate::zaphkiel::smoothing::{exponential_decay, DEFAULT_DECAY};
+use crate::zaphkiel::tween::{Tween, TweenTarget};
+use crate::{AppSystems, ParrylordSingleton, PausableSystems};
+use avian2d::prelude::{Collider, CollisionLayers, Sensor};
+use bevy::ecs::spawn::SpawnIter;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use rand::prelude::SliceRandom;
@@ -30,10 +41,10 @@ pub fn plugin(app: &mut App) {
             aim,
             get_parry_attempt.pipe(handle_parries),
             deal_damage,
-            update_faux,
+            update_power_tiers,
         )
             .run_if(in_state(Screen::Gameplay))
-            .in_set(AppSystems::RecordInput)
+            .in_set(AppSystems::Update)
             .in_set(PausableSystems),
     );
 }
@@ -44,6 +55,7 @@ pub struct FauxPlayerAttackIndicator;
 
 impl FauxPlayerAttackIndicator {
     const MAX_SCALE: f32 = 0.75;
+    const LIFETIME: f32 = 0.15;
 
     pub fn bundle(player_assets: &PlayerAssets, transform: Transform) -> impl Bundle {
         (
@@ -55,25 +67,24 @@ impl FauxPlayerAttackIndicator {
                 ..default()
             },
             transform,
-            Ttl::new(0.15),
+            Ttl::new(Self::LIFETIME),
+            Tween::new(
+                TweenTarget::Scale {
+                    start: Vec3::ZERO,
+                    end: Vec3::splat(Self::MAX_SCALE),
+                },
+                Self::LIFETIME,
+            ),
         )
     }
 }
 
-fn update_faux(mut query: Query<(&mut Transform, &Ttl), With<FauxPlayerAttackIndicator>>) {
-    for (mut tf, ttl) in &mut query {
-        *tf = tf.with_scale(Vec3::splat(
-            ttl.0.fraction() * FauxPlayerAttackIndicator::MAX_SCALE,
-        ));
-    }
-}
-
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[reflect(Component)]
 pub struct PlayerAttackIndicator;
 
 impl PlayerAttackIndicator {
-    pub fn bundle(player_assets: &PlayerAssets) -> impl Bundle {
+    pub fn bundle(_player_assets: &PlayerAssets) -> impl Bundle {
         (
             // StateScoped(Screen::Gameplay),
             Name::new("PlayerAttackIndicator"),
@@ -94,82 +105,42 @@ impl PlayerAttackIndicator {
                 [CollisionLayer::EnemyProjectile],
             ),
             Sensor,
-            CollidingEntities::default(),
         )
     }
 }
 
 fn aim(
-    window: Single<&Window>,
+    player_input: Res<PlayerInput>,
     mut attack_indicator: Query<
         (&mut Transform, &GlobalTransform),
         (With<PlayerAttackIndicator>, Without<Player>),
     >,
-    camera: Single<(&Camera, &GlobalTransform)>,
     time: Res<Time>,
 ) -> Result {
-    let window = *window;
-
-    let Some(mouse) = window.cursor_position() else {
+    let Some(cursor) = player_input.cursor else {
         return Ok(());
     };
 
     let (mut attack_indicator, gt) = attack_indicator.single_mut()?;
-    let (camera, camera_transform) = *camera;
-
-    let alpha = angle_to_mouse_from_global_transform(mouse, gt, camera, camera_transform);
 
-    let mut curr_quat = attack_indicator.rotation;
-    let mut target_quat = Quat::from_rotation_z(alpha);
+    let alpha = angle_to_cursor_from_global_transform(cursor, gt);
 
-    let dot = curr_quat.dot(target_quat);
-    if dot < 0.0 {
-        curr_quat = -curr_quat;
-        target_quat = -target_quat;
-    }
+    let target_quat = Quat::from_rotation_z(alpha);
 
-    // https://docs.rs/glam/0.29.3/src/glam/f32/sse2/quat.rs.html#665
-    // Note that a rotation can be represented by two quaternions: `q` and
-    // `-q`. The slerp path between `q` and `end` will be different from the
-    // path between `-q` and `end`. One path will take the long way around and
-    // one will take the short way. In order to correct for this, the `dot`
-    // product between `self` and `end` should be positive. If the `dot`
-    // product is negative, slerp between `self` and `-end`.
-    // let mut dot = self.dot(end);
-    // if dot < 0.0 {
-    //     end = -end;
-    //     dot = -dot;
-    // }
-
-    let interpolated_quat = exponential_decay!(
-        current: curr_quat,
-        target: target_quat,
-        delta: time.delta_secs(),
-    )
-    .normalize();
-
-    attack_indicator.rotation = interpolated_quat;
+    attack_indicator.rotation = exponential_decay(
+        attack_indicator.rotation,
+        target_quat,
+        DEFAULT_DECAY,
+        time.delta_secs(),
+    );
 
     Ok(())
 }
 
-fn angle_to_mouse_from_global_transform(
-    mouse: Vec2,
-    gt: &GlobalTransform,
-    camera: &Camera,
-    camera_transform: &GlobalTransform,
-) -> f32 {
-    let Ok(pos) = camera.viewport_to_world(camera_transform, mouse) else {
-        panic!(
-            "Ok(pos) = camera.viewport_to_world(camera_transform: {camera_transform:?}, mouse: {mouse:?}): {:?}",
-            camera.viewport_to_world(camera_transform, mouse)
-        );
-    };
-    let pos = pos.origin.truncate();
-
-    let vec_to_mouse = (pos.extend(gt.translation().z) - gt.translation()).normalize_or_zero();
+fn angle_to_cursor_from_global_transform(cursor: Vec2, gt: &GlobalTransform) -> f32 {
+    let vec_to_cursor = (cursor.extend(gt.translation().z) - gt.translation()).normalize_or_zero();
 
-    vec_to_mouse.y.atan2(vec_to_mouse.x)
+    vec_to_cursor.y.atan2(vec_to_cursor.x)
 }
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
@@ -177,24 +148,37 @@ fn angle_to_mouse_from_global_transform(
 pub struct PlayerAttack;
 
 impl PlayerAttack {
+    /// How much bigger the halo of a [`PowerTier`] with a glow is than the attack itself.
+    const GLOW_SIZE: f32 = 1.6;
+
+    /// Spawn with [`ProjectilePool::spawn`].
     pub fn bundle(
         power: u32,
         attack_assets: &AttackAssets,
         pos: Vec2,
-        velocity: LinearVelocity,
+        velocity: Vec2,
         ttl: Ttl,
     ) -> impl Bundle {
+        let tier = PowerTier::from_power(power);
+
+        let glow = (tier.glow > 0.0).then(|| {
+            (
+                Name::new("Glow"),
+                Self::glow_sprite(tier, attack_assets),
+                // Behind the attack.
+                Transform::from_xyz(0.0, 0.0, -0.1),
+            )
+        });
+
         (
             StateScoped(Screen::Gameplay),
             Self,
             Attack(power),
+            AttackPayload::from_power(power),
+            Clashes::default(),
             Transform::from_xyz(pos.x, pos.y, 3.0).with_scale(Vec3::splat(0.1)),
-            Sprite {
-                image: Self::get_sprite((power % AttackAssets::MAX as u32) as u8, attack_assets),
-                color: Color::srgb(0.1, 0.1, 30.0),
-                ..default()
-            },
-            Collider::circle(128.0),
+            Self::sprite(tier, attack_assets),
+            Children::spawn(SpawnIter(glow.into_iter())),
             CollisionLayers::new(
                 [CollisionLayer::PlayerProjectile],
                 [
@@ -203,62 +187,80 @@ impl PlayerAttack {
                     CollisionLayer::Walls,
                 ],
             ),
-            RigidBody::Dynamic,
-            Sensor,
-            velocity,
-            AngularVelocity(-3.0),
+            ProjectileMotion::new(velocity).with_spin(-3.0),
             ttl,
-            CollidingEntities::default(),
         )
     }
 
-    fn get_sprite(power: u8, attack_assets: &AttackAssets) -> Handle<Image> {
-        let power = power % AttackAssets::MAX;
-        match power {
-            0 => attack_assets._0.clone(),
-            1 => attack_assets._1.clone(),
-            2 => attack_assets._2.clone(),
-            3 => attack_assets._3.clone(),
-            4 => attack_assets._4.clone(),
-            5 => attack_assets._5.clone(),
-            6 => attack_assets._6.clone(),
-            7 => attack_assets._7.clone(),
-            8 => attack_assets._8.clone(),
-            9 => attack_assets._9.clone(),
-            10 => attack_assets._10.clone(),
-            11 => attack_assets._11.clone(),
-            _what => unreachable!("get_sprite: {_what}"),
+    fn sprite(tier: PowerTier, attack_assets: &AttackAssets) -> Sprite {
+        #[allow(clippy::cast_precision_loss)]
+        let size = AttackAssets::FRAME_SIZE as f32 * tier.size;
+
+        Sprite {
+            color: tier.color,
+            custom_size: Some(Vec2::splat(size)),
+            ..attack_assets.sprite(tier.frame)
+        }
+    }
+
+    fn glow_sprite(tier: PowerTier, attack_assets: &AttackAssets) -> Sprite {
+        let sprite = Self::sprite(tier, attack_assets);
+
+        Sprite {
+            color: tier.color.with_alpha(tier.glow),
+            custom_size: sprite.custom_size.map(|it| it * Self::GLOW_SIZE),
+            ..sprite
+        }
+    }
+}
+
+/// Keeps attacks looking and hitting as strong as they are, since clashes take their power away.
+fn update_power_tiers(
+    attack_assets: Res<AttackAssets>,
+    mut attacks: Query<
+        (&Attack, &mut AttackPayload, &mut Sprite, Option<&Children>),
+        (With<PlayerAttack>, Changed<Attack>),
+    >,
+    mut glows: Query<&mut Sprite, Without<PlayerAttack>>,
+) {
+    for (attack, mut payload, mut sprite, children) in &mut attacks {
+        let tier = PowerTier::from_power(attack.0);
+        *sprite = PlayerAttack::sprite(tier, &attack_assets);
+        *payload = AttackPayload {
+            // Enemies already gone through stay gone through.
+            pierce: payload
+                .pierce
+                .min(AttackPayload::from_power(attack.0).pierce),
+            ..AttackPayload::from_power(attack.0)
+        };
+
+        for &child in children.into_iter().flatten() {
+            if let Ok(mut glow) = glows.get_mut(child) {
+                *glow = PlayerAttack::glow_sprite(tier, &attack_assets);
+            }
         }
     }
 }
 
 pub fn get_parry_attempt(
-    query: Single<&CollidingEntities, (With<PlayerAttackIndicator>, Without<Player>)>,
-    all_player_projectiles: Query<Entity, With<PlayerAttack>>,
-    mouse_input: Res<ButtonInput<MouseButton>>,
+    enemy_attacks: Query<(Entity, &ProjectileHits), With<EnemyAttack>>,
+    player_input: Res<PlayerInput>,
     mut commands: Commands,
     player_assets: Res<PlayerAssets>,
-    player_attack_indicator: Single<&GlobalTransform, With<PlayerAttackIndicator>>,
-    window: Single<&Window>,
-    camera: Single<(&Camera, &GlobalTransform)>,
+    player_attack_indicator: Single<(Entity, &GlobalTransform), With<PlayerAttackIndicator>>,
 ) -> Vec<Entity> {
-    if !mouse_input.just_pressed(MouseButton::Left) {
+    let (indicator, player_attack_indicator) = *player_attack_indicator;
+
+    if !player_input.parry {
         return Vec::new();
     }
 
-    let window = *window;
-    let Some(mouse) = window.cursor_position() else {
-        warn!("Some(mouse) = window.cursor_position()");
+    let Some(cursor) = player_input.cursor else {
+        warn!("Some(cursor) = player_input.cursor");
         return Vec::new();
     };
-    let (camera, camera_transform) = *camera;
 
-    let angle = angle_to_mouse_from_global_transform(
-        mouse,
-        *player_attack_indicator,
-        camera,
-        camera_transform,
-    );
+    let angle = angle_to_cursor_from_global_transform(cursor, player_attack_indicator);
 
     let translation = player_attack_indicator.translation();
     let rotation = Quat::from_axis_angle(Vec3::Z, angle);
@@ -270,10 +272,10 @@ pub fn get_parry_attempt(
 
     commands.spawn(FauxPlayerAttackIndicator::bundle(&player_assets, transform));
 
-    query
+    enemy_attacks
         .iter()
-        .copied()
-        .filter(|e| !all_player_projectiles.contains(*e))
+        .filter(|(_, hits)| hits.contains(&indicator))
+        .map(|(entity, _)| entity)
         .collect()
 }
 
@@ -281,14 +283,16 @@ pub fn handle_parries(
     In(entities): In<Vec<Entity>>,
     mut commands: Commands,
     change_components: Query<
-        (&LinearVelocity, &Transform, &Ttl),
+        (&ProjectileMotion, &Transform, &Ttl, &Attack, Option<&Rally>),
         (With<EnemyAttack>, Without<PlayerAttack>),
     >,
     player_attack_indicator: Single<&GlobalTransform, With<PlayerAttackIndicator>>,
     attack_assets: Res<AttackAssets>,
-    window: Single<&Window>,
-    camera: Single<(&Camera, &GlobalTransform)>,
+    player_input: Res<PlayerInput>,
     mut singleton: ResMut<ParrylordSingleton>,
+    mut telemetry: EventWriter<TelemetryEvent>,
+    mut pool: ResMut<ProjectilePool>,
+    parry_homing: Res<ParryHoming>,
 ) {
     let Some(&entity) = entities.first() else {
         // warn!("Some(&entity) = entities.get(0)");
@@ -302,7 +306,7 @@ pub fn handle_parries(
     let Some((sum_speed, sum_pos, sum_ttl, total)) = entities
         .iter()
         .flat_map(|&x| change_components.get(x))
-        .map(|(x, y, z)| (x.length(), y.translation, z.0.remaining_secs()))
+        .map(|(x, y, z, _, _)| (x.velocity.length(), y.translation, z.0.remaining_secs()))
         .map(|(x, y, z)| (x, y.truncate(), z, 1u32))
         .reduce(|(a, b, c, d), (x, y, z, w)| (a + x, b + y, c + z, d + w))
     else {
@@ -315,40 +319,57 @@ pub fn handle_parries(
     #[allow(clippy::cast_precision_loss)]
     let total_f32 = total as f32;
 
-    let window = *window;
-    let Some(mouse) = window.cursor_position() else {
-        warn!("Some(mouse) = window.cursor_position()");
+    let Some(cursor) = player_input.cursor else {
+        warn!("Some(cursor) = player_input.cursor");
         return;
     };
-    let (camera, camera_transform) = *camera;
 
-    let angle = angle_to_mouse_from_global_transform(
-        mouse,
-        *player_attack_indicator,
-        camera,
-        camera_transform,
-    );
+    let angle = angle_to_cursor_from_global_transform(cursor, *player_attack_indicator);
     let angle = Vec2::from_angle(angle);
 
     let pos = sum_pos / total_f32;
-    let velocity = LinearVelocity(angle * sum_speed / total_f32);
+    let velocity = angle * sum_speed / total_f32;
     let ttl = Ttl::new((sum_ttl / total_f32) + 1.0);
 
-    let power = 2u32.saturating_pow(total - 1);
-
-    commands.spawn(PlayerAttack::bundle(
+    // Attacks elites sent back keep their power, and keep the rally going.
+    let parried = entities.iter().flat_map(|&x| change_components.get(x));
+    let strongest = parried
+        .clone()
+        .map(|(.., attack, _)| attack.0)
+        .max()
+        .unwrap_or(1);
+    let rally = parried
+        .filter_map(|(.., rally)| rally)
+        .max_by_key(|it| it.0);
+
+    let power = 2u32.saturating_pow(total - 1).saturating_mul(strongest);
+
+    telemetry.write(TelemetryEvent::Parry {
+        projectiles: total,
         power,
-        &attack_assets,
-        pos,
-        velocity,
-        ttl,
-    ));
+    });
+
+    let attack = pool.spawn(
+        &mut commands,
+        PlayerAttack::bundle(power, &attack_assets, pos, velocity, ttl),
+    );
+    if let Some(homing) = parry_homing.for_parry(total) {
+        commands.entity(attack).insert(homing);
+    }
 
     for entity in entities {
-        let Ok(mut entity) = commands.get_entity(entity) else {
-            continue;
-        };
-        entity.try_despawn();
+        commands.trigger_targets(ReleaseProjectile, entity);
+    }
+
+    if rally.is_some() {
+        let rally = Rally::extend(rally, &mut singleton, &mut telemetry);
+        commands.entity(attack).insert(rally);
+        // Sounds different from a normal parry.
+        commands.trigger(RallyHit {
+            position: pos,
+            rally: rally.0,
+        });
+        return;
     }
 
     commands.spawn(sound_effect(
@@ -361,47 +382,64 @@ pub fn handle_parries(
 }
 
 pub fn deal_damage(
-    query: Query<
+    mut query: Query<
         (
-            &CollidingEntities,
+            &ProjectileHits,
             &Attack,
+            &mut AttackPayload,
             Entity,
             &Transform,
-            &LinearVelocity,
+            &ProjectileMotion,
             &Ttl,
+            Option<&Homing>,
         ),
         (With<PlayerAttack>, Without<InvincibilityTimer>),
     >,
+    projectiles: Query<(), (With<Projectile>, Without<FadingProjectile>)>,
     mut enemies: Query<&mut Health, Without<InvincibilityTimer>>,
     walls: Query<Entity, With<Wall>>,
     mut commands: Commands,
     attack_assets: Res<AttackAssets>,
+    mut rng: ResMut<GameRng>,
+    mut telemetry: EventWriter<TelemetryEvent>,
+    config: Res<ProjectileConfig>,
+    mut pool: ResMut<ProjectilePool>,
 ) {
-    let mut thread_rng = rand::thread_rng();
-
     let mut spawn_sfx = false;
 
-    'outer: for (n, (colliding_entities, attack, attack_entity, transform, velocity, ttl)) in
-        query.iter().enumerate()
+    // Live attacks, counting the ones split off so far this frame. Near the cap, attacks split
+    // into as many pieces as there's room for, and stay in one piece once there's none.
+    let mut live = projectiles.iter().len();
+
+    'outer: for (
+        n,
+        (colliding_entities, attack, mut payload, attack_entity, transform, motion, ttl, homing),
+    ) in query.iter_mut().enumerate()
     {
         for &entity in colliding_entities.iter() {
-            if walls.contains(entity) || enemies.contains(entity) {
-                let Ok(mut attack_entity) = commands.get_entity(attack_entity) else {
-                    continue;
-                };
-                attack_entity.try_despawn();
+            if enemies.contains(entity) && payload.pierce > 0 {
+                // Goes through, and the enemy is invincible by the time it gets out the other side.
+                payload.pierce -= 1;
+            } else if walls.contains(entity) || enemies.contains(entity) {
+                commands.trigger_targets(ReleaseProjectile, attack_entity);
+                live = live.saturating_sub(1);
 
                 let power = attack.0.saturating_sub(1);
 
                 if power != 0 && n < 256 {
-                    let dir = velocity.normalize().to_angle();
-                    let speed = velocity.length();
+                    let dir = motion.velocity.normalize().to_angle();
+                    let speed = motion.velocity.length();
                     let ttl = ttl.0.remaining_secs().mul_add(0.5, 1.0);
 
-                    for _ in 0..=power.isqrt() {
-                        let dir = dir
-                            + thread_rng.gen_range((-FRAC_PI_8 / 2.0)..(FRAC_PI_8 / 2.0))
-                            + FRAC_PI_2;
+                    let room = config.max_projectiles.saturating_sub(live).max(1);
+                    let splits = usize::try_from(power.isqrt() + 1)
+                        .unwrap_or(usize::MAX)
+                        .min(room);
+                    live += splits;
+
+                    for _ in 0..splits {
+                        let dir =
+                            dir + rng.gen_range((-FRAC_PI_8 / 2.0)..(FRAC_PI_8 / 2.0)) + FRAC_PI_2;
                         let dir = Vec2::from_angle(dir);
                         let ttl = Ttl::new(ttl);
 
@@ -409,11 +447,16 @@ pub fn deal_damage(
                             power,
                             &attack_assets,
                             transform.translation.truncate(),
-                            LinearVelocity(dir * speed),
+                            dir * speed,
                             ttl,
                         );
 
-                        let id = commands.spawn(new_attack).id();
+                        let id = pool.spawn(&mut commands, new_attack);
+
+                        // Splits keep homing in on whatever's left.
+                        if let Some(&homing) = homing {
+                            commands.entity(id).insert(homing);
+                        }
 
                         if walls.contains(entity) {
                             commands
@@ -435,6 +478,20 @@ pub fn deal_damage(
 
             health.0 = health.0.saturating_sub(attack.0);
 
+            telemetry.write(TelemetryEvent::Damage {
+                target: DamageTarget::Enemy,
+                amount: attack.0,
+                remaining_health: health.0,
+            });
+
+            commands.trigger_targets(
+                ApplyPayload {
+                    payload: *payload,
+                    direction: motion.velocity,
+                },
+                entity,
+            );
+
             commands
                 .entity(entity)
                 .insert(InvincibilityTimer(Timer::from_seconds(
@@ -448,7 +505,7 @@ pub fn deal_damage(
         commands.spawn(sound_effect(
             attack_assets
                 .attack_sfx
-                .choose(&mut thread_rng)
+                .choose(&mut rand::thread_rng())
                 .expect("should exist")
                 .clone(),
         ));
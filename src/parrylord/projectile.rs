@@ -0,0 +1,292 @@
+//! Attacks, moved without the physics solver and reused instead of despawned.
+//!
+//! Attacks aren't rigid bodies. [`ProjectileMotion`] moves them every frame, and a shape cast
+//! along the way finds what they touched ([`ProjectileHits`]), in the layers their
+//! [`CollisionLayers`] collide with.
+//!
+//! Attacks are spawned through the [`ProjectilePool`] and handed back with [`ReleaseProjectile`]
+//! instead of being despawned. Pooled entities are [`Disabled`], which hides them from gameplay
+//! and rendering until they're reused.
+//!
+//! There's also a cap on how many attacks can be live at once ([`ProjectileConfig`]). Past it, the
+//! oldest fade out without dealing damage, and attacks that would split merge instead.
+
+use crate::parrylord::attack::{Attack, AttackPayload};
+use crate::parrylord::clash::Clashes;
+use crate::parrylord::deflect::Rally;
+use crate::parrylord::enemy_attack::EnemyAttack;
+use crate::parrylord::health::InvincibilityTimer;
+use crate::parrylord::homing::Homing;
+use crate::parrylord::player_attack::PlayerAttack;
+use crate::parrylord::ttl::Ttl;
+use crate::screens::Screen;
+use crate::zaphkiel::tween::{Tween, TweenTarget};
+use crate::{AppSystems, PausableSystems};
+use avian2d::prelude::{
+    Collider, CollisionLayers, ShapeCastConfig, SpatialQuery, SpatialQueryFilter,
+};
+use bevy::ecs::entity_disabling::Disabled;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<ProjectileConfig>();
+    app.init_resource::<ProjectileConfig>();
+    app.register_type::<ProjectilePool>();
+    app.init_resource::<ProjectilePool>();
+    app.register_type::<Projectile>();
+    app.register_type::<FadingProjectile>();
+    app.register_type::<ProjectileMotion>();
+    app.register_type::<ProjectileHits>();
+
+    app.add_observer(release_projectile);
+
+    app.add_systems(
+        Update,
+        (
+            // Gameplay reads the hits.
+            move_projectiles
+                .after(AppSystems::RecordInput)
+                .before(AppSystems::Update),
+            fade_oldest_projectiles.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), clear_projectile_pool);
+}
+
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct ProjectileConfig {
+    /// How many attacks can be live at once, not counting ones fading out.
+    pub max_projectiles: usize,
+}
+
+impl Default for ProjectileConfig {
+    fn default() -> Self {
+        Self {
+            max_projectiles: 1024,
+        }
+    }
+}
+
+/// An attack spawned through the [`ProjectilePool`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+#[require(ProjectileMotion, ProjectileHits)]
+pub struct Projectile {
+    /// Increases with every spawn, so older attacks have lower serials.
+    serial: u64,
+}
+
+impl Projectile {
+    /// The radius attacks hit things in, in world units.
+    pub const RADIUS: f32 = 12.8;
+}
+
+/// How an attack moves.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct ProjectileMotion {
+    pub velocity: Vec2,
+    /// Added to the velocity every second.
+    pub acceleration: Vec2,
+    /// How fast the velocity turns, in radians per second, for curved paths.
+    pub turn_rate: f32,
+    /// How fast the sprite spins, in radians per second. Only for looks.
+    pub spin: f32,
+}
+
+impl ProjectileMotion {
+    pub fn new(velocity: Vec2) -> Self {
+        Self {
+            velocity,
+            ..default()
+        }
+    }
+
+    pub fn with_spin(mut self, spin: f32) -> Self {
+        self.spin = spin;
+        self
+    }
+}
+
+/// What an attack touched while moving this frame.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect, Deref)]
+#[reflect(Component)]
+pub struct ProjectileHits(Vec<Entity>);
+
+/// An attack over the cap, on its way back to the pool.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct FadingProjectile;
+
+/// Triggered on an attack to put it back in the [`ProjectilePool`]. Does nothing if it's already
+/// there.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReleaseProjectile;
+
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct ProjectilePool {
+    /// Disabled attacks, ready to be reused.
+    free: Vec<Entity>,
+    next_serial: u64,
+}
+
+impl ProjectilePool {
+    /// Spawns an attack, reusing a pooled entity if there is one.
+    pub fn spawn(&mut self, commands: &mut Commands, bundle: impl Bundle) -> Entity {
+        let projectile = Projectile {
+            serial: self.next_serial,
+        };
+        self.next_serial += 1;
+
+        match self.free.pop() {
+            Some(entity) => commands
+                .entity(entity)
+                .remove::<Disabled>()
+                .insert((projectile, bundle))
+                .id(),
+            None => commands.spawn((projectile, bundle)).id(),
+        }
+    }
+
+    /// How many attacks are waiting to be reused.
+    pub fn available(&self) -> usize {
+        self.free.len()
+    }
+}
+
+fn release_projectile(
+    trigger: Trigger<ReleaseProjectile>,
+    // Pooled attacks are disabled, so they don't match.
+    projectiles: Query<(), With<Projectile>>,
+    mut pool: ResMut<ProjectilePool>,
+    mut commands: Commands,
+) {
+    let entity = trigger.target();
+    if !projectiles.contains(entity) {
+        return;
+    }
+
+    commands
+        .entity(entity)
+        .despawn_related::<Children>()
+        .remove::<(
+            PlayerAttack,
+            EnemyAttack,
+            Attack,
+            AttackPayload,
+            Ttl,
+            InvincibilityTimer,
+            Tween,
+            FadingProjectile,
+            Clashes,
+            Homing,
+            Rally,
+        )>()
+        .insert((
+            Disabled,
+            Visibility::Inherited,
+            ProjectileMotion::default(),
+            ProjectileHits::default(),
+        ));
+    pool.free.push(entity);
+}
+
+pub fn move_projectiles(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut projectiles: Query<(
+        &mut ProjectileMotion,
+        &mut Transform,
+        &mut ProjectileHits,
+        Option<&CollisionLayers>,
+        Has<FadingProjectile>,
+    )>,
+) {
+    let delta = time.delta_secs();
+    let shape = Collider::circle(Projectile::RADIUS);
+
+    projectiles
+        .par_iter_mut()
+        .for_each(|(mut motion, mut transform, mut hits, layers, fading)| {
+            let turn = Rot2::radians(motion.turn_rate * delta);
+            motion.velocity = turn * motion.velocity + motion.acceleration * delta;
+
+            let start = transform.translation.truncate();
+            let displacement = motion.velocity * delta;
+            transform.translation += displacement.extend(0.0);
+            transform.rotate_z(motion.spin * delta);
+
+            hits.0.clear();
+            // Fading attacks are harmless.
+            let Some(layers) = layers.filter(|_| !fading) else {
+                return;
+            };
+
+            let filter = SpatialQueryFilter::from_mask(layers.filters);
+            match Dir2::new(displacement) {
+                Ok(direction) => spatial_query.shape_hits_callback(
+                    &shape,
+                    start,
+                    0.0,
+                    direction,
+                    &ShapeCastConfig::from_max_distance(displacement.length()),
+                    &filter,
+                    |hit| {
+                        hits.0.push(hit.entity);
+                        true
+                    },
+                ),
+                // Not moving.
+                Err(_) => hits
+                    .0
+                    .extend(spatial_query.shape_intersections(&shape, start, 0.0, &filter)),
+            }
+        });
+}
+
+/// Fades out the oldest attacks while there are more than [`ProjectileConfig::max_projectiles`].
+fn fade_oldest_projectiles(
+    config: Res<ProjectileConfig>,
+    projectiles: Query<(Entity, &Projectile), Without<FadingProjectile>>,
+    mut commands: Commands,
+) {
+    const FADE_TIME: f32 = 0.25;
+
+    let excess = projectiles
+        .iter()
+        .len()
+        .saturating_sub(config.max_projectiles);
+    if excess == 0 {
+        return;
+    }
+
+    let mut oldest = projectiles.iter().collect::<Vec<_>>();
+    oldest.select_nth_unstable_by_key(excess - 1, |(_, projectile)| projectile.serial);
+
+    for &(entity, _) in &oldest[..excess] {
+        commands.entity(entity).try_insert((
+            FadingProjectile,
+            Ttl::new(FADE_TIME),
+            Tween::new(
+                TweenTarget::SpriteAlpha {
+                    start: 1.0,
+                    end: 0.0,
+                },
+                FADE_TIME,
+            ),
+        ));
+    }
+}
+
+/// Pooled attacks are disabled, so they aren't cleaned up with the rest of the level.
+fn clear_projectile_pool(mut pool: ResMut<ProjectilePool>, mut commands: Commands) {
+    for entity in pool.free.drain(..) {
+        if let Ok(mut entity) = commands.get_entity(entity) {
+            entity.try_despawn();
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! Recording and playing back the inputs of a run.
+//!
+//! A [`Replay`] stores the seed, mode and starting level of a run together with the
+//! [`PlayerInput`] and frame time of every gameplay frame. Playing it back feeds those inputs and
+//! frame times into the same systems, which reproduces the run.
+
+use crate::parrylord::input::{read_player_input, PlayerInput};
+use crate::parrylord::level::{spawn_level, StartingLevel};
+use crate::parrylord::mode::GameMode;
+use crate::parrylord::rng::RunSeed;
+use crate::screens::Screen;
+use crate::{AppSystems, PausableSystems};
+use base64::Engine;
+use bevy::prelude::*;
+use bevy::time::{TimeSystem, TimeUpdateStrategy};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::time::Duration;
+
+pub fn plugin(app: &mut App) {
+    app.add_event::<ReplayFinished>();
+    app.init_resource::<ReplayRecorder>();
+
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (discard_fixed_overstep, start_recording.after(spawn_level)),
+    );
+
+    app.add_systems(
+        First,
+        apply_replay_frame_time
+            .before(TimeSystem)
+            .run_if(resource_exists::<ReplayPlayback>),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            play_back_input.run_if(resource_exists::<ReplayPlayback>),
+            record_frame.after(read_player_input),
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(AppSystems::RecordInput)
+            .in_set(PausableSystems),
+    );
+
+    #[cfg(not(target_family = "wasm"))]
+    app.add_systems(
+        OnExit(Screen::Gameplay),
+        save_replay.run_if(resource_exists::<ReplayOutput>),
+    );
+}
+
+/// Everything needed to reproduce a run.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Replay {
+    pub seed: u64,
+    pub mode: GameMode,
+    pub start_level: u32,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    /// The replay as deflated JSON, encoded as base64 so it fits in a JSON string.
+    pub fn compress(&self) -> std::io::Result<String> {
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+        serde_json::to_writer(&mut encoder, self)?;
+        let bytes = encoder.finish()?;
+
+        Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    /// Reads a replay made by [`Replay::compress`].
+    pub fn decompress(compressed: &str) -> std::io::Result<Self> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(compressed)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        let mut json = Vec::new();
+        flate2::read::DeflateDecoder::new(bytes.as_slice()).read_to_end(&mut json)?;
+
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+/// The [`PlayerInput`] of a single frame, along with how long that frame took.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ReplayFrame {
+    /// Frame time in nanoseconds.
+    pub delta: u64,
+    pub movement: [f32; 2],
+    pub cursor: Option<[f32; 2]>,
+    pub parry: bool,
+}
+
+impl ReplayFrame {
+    fn new(delta: Duration, input: &PlayerInput) -> Self {
+        Self {
+            delta: u64::try_from(delta.as_nanos()).unwrap_or(u64::MAX),
+            movement: input.movement.to_array(),
+            cursor: input.cursor.as_ref().map(Vec2::to_array),
+            parry: input.parry,
+        }
+    }
+
+    fn input(&self) -> PlayerInput {
+        PlayerInput {
+            movement: Vec2::from_array(self.movement),
+            cursor: self.cursor.map(Vec2::from_array),
+            parry: self.parry,
+        }
+    }
+}
+
+/// The replay of the current (or last) run.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ReplayRecorder(pub Replay);
+
+/// A replay that is being played back instead of reading the keyboard and mouse.
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    /// Index of the next frame to play.
+    pub cursor: usize,
+}
+
+impl ReplayPlayback {
+    pub const fn new(replay: Replay) -> Self {
+        Self { replay, cursor: 0 }
+    }
+}
+
+/// Sent when a [`ReplayPlayback`] runs out of frames.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ReplayFinished;
+
+/// Where to save the replay of every finished run.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayOutput(pub std::path::PathBuf);
+
+/// The fixed timestep (and with it physics) must start every run from the same point, or a replay
+/// would step physics at different times than the original run did.
+fn discard_fixed_overstep(mut time: ResMut<Time<Fixed>>) {
+    let overstep = time.overstep();
+    time.discard_overstep(overstep);
+}
+
+fn start_recording(
+    mut recorder: ResMut<ReplayRecorder>,
+    seed: Res<RunSeed>,
+    mode: Res<GameMode>,
+    starting_level: Res<StartingLevel>,
+) {
+    recorder.0 = Replay {
+        seed: seed.0,
+        mode: *mode,
+        start_level: starting_level.0,
+        frames: Vec::new(),
+    };
+}
+
+fn record_frame(mut recorder: ResMut<ReplayRecorder>, input: Res<PlayerInput>, time: Res<Time>) {
+    recorder
+        .0
+        .frames
+        .push(ReplayFrame::new(time.delta(), &input));
+}
+
+/// Frame times are part of the replay, so they're applied before [`Time`] updates.
+fn apply_replay_frame_time(
+    playback: Res<ReplayPlayback>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+) {
+    if let Some(frame) = playback.replay.frames.get(playback.cursor) {
+        *time_update_strategy =
+            TimeUpdateStrategy::ManualDuration(Duration::from_nanos(frame.delta));
+    }
+}
+
+fn play_back_input(
+    mut playback: ResMut<ReplayPlayback>,
+    mut input: ResMut<PlayerInput>,
+    mut time_update_strategy: ResMut<TimeUpdateStrategy>,
+    mut finished: EventWriter<ReplayFinished>,
+    mut commands: Commands,
+) {
+    let Some(frame) = playback.replay.frames.get(playback.cursor) else {
+        info!("replay finished after {} frames", playback.cursor);
+        *input = PlayerInput::default();
+        *time_update_strategy = TimeUpdateStrategy::default();
+        commands.remove_resource::<ReplayPlayback>();
+        finished.write(ReplayFinished);
+        return;
+    };
+
+    *input = frame.input();
+    playback.cursor += 1;
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn save_replay(recorder: Res<ReplayRecorder>, output: Res<ReplayOutput>) {
+    let result = std::fs::File::create(&output.0)
+        .map(std::io::BufWriter::new)
+        .and_then(|mut file| {
+            serde_json::to_writer(&mut file, &recorder.0)?;
+            file.flush()
+        });
+
+    match result {
+        Ok(()) => info!("saved replay to {}", output.0.display()),
+        Err(e) => error!(?e),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn load_replay(path: &std::path::Path) -> std::io::Result<Replay> {
+    let file = std::fs::File::open(path).map(std::io::BufReader::new)?;
+    Ok(serde_json::from_reader(file)?)
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<RunSeed>();
+    app.init_resource::<RunSeed>();
+    app.init_resource::<GameRng>();
+    app.init_resource::<FixedSeed>();
+}
+
+/// A seed every run should use instead of a random one, e.g. to reproduce a specific run.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FixedSeed(pub Option<u64>);
+
+/// The seed the current run's [`GameRng`] was created from.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Resource)]
+pub struct RunSeed(pub u64);
+
+/// The random number generator behind every gameplay decision.
+///
+/// Cosmetic randomness (e.g. which sound effect plays) doesn't need to go through this.
+#[derive(Resource, Debug, Clone, Deref, DerefMut)]
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn from_seed(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(RunSeed::default().0)
+    }
+}
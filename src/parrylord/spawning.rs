@@ -0,0 +1,233 @@
+//! Where enemies spawn, and them arriving through a portal.
+//!
+//! The [`SpawnDirector`] keeps new enemies away from the player and from each other. Enemies then
+//! spend a moment [`Spawning`]: they grow out of a portal, and can't hurt or be hurt until they're
+//! through.
+
+use crate::assets::AttackAssets;
+use crate::parrylord::ttl::Ttl;
+use crate::screens::Screen;
+use crate::zaphkiel::tween::{Tween, TweenTarget};
+use crate::{AppSystems, PausableSystems};
+use avian2d::prelude::CollisionLayers;
+use bevy::prelude::*;
+use rand::Rng;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<SpawnDirector>();
+    app.init_resource::<SpawnDirector>();
+    app.register_type::<Spawning>();
+    app.register_type::<Portal>();
+
+    app.add_observer(start_spawning);
+
+    app.add_systems(
+        Update,
+        (
+            tick_spawning.in_set(AppSystems::TickTimers),
+            finish_spawning.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Where enemies can spawn in the waves from some level on.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct WaveSpawns {
+    /// The first level these apply to. They apply until the next [`WaveSpawns`] takes over.
+    pub from_level: u32,
+    /// Where enemies can spawn, unless there are [`Self::points`].
+    pub area: Rect,
+    /// Fixed spots enemies spawn at. If there are any, the [`Self::area`] isn't used.
+    pub points: Vec<Vec2>,
+    /// How close enemies can spawn to the player.
+    pub min_player_distance: f32,
+    /// How close enemies can spawn to each other.
+    pub min_enemy_distance: f32,
+}
+
+impl Default for WaveSpawns {
+    fn default() -> Self {
+        Self {
+            from_level: 1,
+            area: Rect::new(-600.0, -200.0, 600.0, 200.0),
+            points: Vec::new(),
+            min_player_distance: 350.0,
+            min_enemy_distance: 160.0,
+        }
+    }
+}
+
+impl WaveSpawns {
+    fn random_point(&self, rng: &mut impl Rng) -> Vec2 {
+        if !self.points.is_empty() {
+            return self.points[rng.gen_range(0..self.points.len())];
+        }
+
+        let x = rng.gen_range(self.area.min.x..self.area.max.x);
+        let y = rng.gen_range(self.area.min.y..self.area.max.y);
+        Vec2::new(x, y)
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub struct SpawnDirector {
+    /// Sorted by [`WaveSpawns::from_level`].
+    pub waves: Vec<WaveSpawns>,
+    /// How many spots to try before settling for the roomiest one.
+    pub attempts: u32,
+    /// How long enemies take to come through their portal, in seconds.
+    pub portal_secs: f32,
+}
+
+impl Default for SpawnDirector {
+    fn default() -> Self {
+        Self {
+            waves: vec![WaveSpawns::default()],
+            attempts: 16,
+            portal_secs: 0.8,
+        }
+    }
+}
+
+impl SpawnDirector {
+    /// The spawn rules for a level.
+    pub fn wave(&self, level: u32) -> WaveSpawns {
+        self.waves
+            .iter()
+            .take_while(|it| it.from_level <= level)
+            .last()
+            .or(self.waves.first())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Picks a spot for an enemy far enough from the `player` and the other `enemies`. If there
+    /// isn't one, picks the spot that comes closest.
+    pub fn position(&self, level: u32, rng: &mut impl Rng, player: Vec2, enemies: &[Vec2]) -> Vec2 {
+        let wave = self.wave(level);
+
+        // How far a spot is from being allowed. Zero or less is fine.
+        let crowding = |spot: Vec2| {
+            let from_player = wave.min_player_distance - spot.distance(player);
+            let from_enemies = enemies
+                .iter()
+                .map(|&enemy| wave.min_enemy_distance - spot.distance(enemy))
+                .fold(f32::NEG_INFINITY, f32::max);
+            from_player.max(from_enemies)
+        };
+
+        let mut best = (f32::INFINITY, Vec2::ZERO);
+        for _ in 0..self.attempts.max(1) {
+            let spot = wave.random_point(rng);
+            let crowding = crowding(spot);
+            if crowding <= 0.0 {
+                return spot;
+            }
+            if crowding < best.0 {
+                best = (crowding, spot);
+            }
+        }
+
+        best.1
+    }
+}
+
+/// Coming through a portal. Until it's done, it doesn't collide with anything.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Spawning {
+    pub timer: Timer,
+    /// The collision layers to go back to once through.
+    layers: Option<CollisionLayers>,
+}
+
+impl Spawning {
+    /// Grows from nothing to `scale` over `secs` seconds.
+    pub fn bundle(scale: f32, secs: f32) -> impl Bundle {
+        (
+            Self {
+                timer: Timer::from_seconds(secs, TimerMode::Once),
+                layers: None,
+            },
+            Tween::new(
+                TweenTarget::Scale {
+                    start: Vec3::ZERO,
+                    end: Vec3::splat(scale),
+                },
+                secs,
+            )
+            .with_ease(EaseFunction::BackOut),
+        )
+    }
+}
+
+/// Where something is [`Spawning`] from. Closes as it comes through.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Portal;
+
+impl Portal {
+    const COLOR: Color = Color::srgb(0.6, 0.2, 1.0);
+    const SIZE: f32 = 0.5;
+    const FRAME: usize = 6;
+
+    pub fn bundle(attack_assets: &AttackAssets, position: Vec2, secs: f32) -> impl Bundle {
+        (
+            StateScoped(Screen::Gameplay),
+            Name::new("Portal"),
+            Self,
+            Sprite {
+                color: Self::COLOR,
+                ..attack_assets.sprite(Self::FRAME)
+            },
+            // Under the enemy.
+            Transform::from_translation(position.extend(0.5)),
+            Ttl::new(secs),
+            Tween::new(
+                TweenTarget::Scale {
+                    start: Vec3::splat(Self::SIZE),
+                    end: Vec3::ZERO,
+                },
+                secs,
+            )
+            .with_ease(EaseFunction::QuadraticIn),
+        )
+    }
+}
+
+fn start_spawning(
+    trigger: Trigger<OnAdd, Spawning>,
+    mut spawning: Query<(&mut Spawning, &mut CollisionLayers)>,
+) {
+    let Ok((mut spawning, mut layers)) = spawning.get_mut(trigger.target()) else {
+        return;
+    };
+
+    spawning.layers = Some(*layers);
+    *layers = CollisionLayers::NONE;
+}
+
+fn tick_spawning(mut spawning: Query<&mut Spawning>, time: Res<Time>) {
+    for mut spawning in &mut spawning {
+        spawning.timer.tick(time.delta());
+    }
+}
+
+fn finish_spawning(
+    mut spawning: Query<(Entity, &Spawning, Option<&mut CollisionLayers>)>,
+    mut commands: Commands,
+) {
+    for (entity, spawning, layers) in &mut spawning {
+        if !spawning.timer.finished() {
+            continue;
+        }
+
+        if let (Some(mut layers), Some(original)) = (layers, spawning.layers) {
+            *layers = original;
+        }
+        commands.entity(entity).remove::<Spawning>();
+    }
+}
@@ -0,0 +1,221 @@
+//! Lasting effects of being hit: knockback, stuns, slows and burns.
+//!
+//! Attacks hand them out by triggering [`ApplyPayload`] on what they hit. A new effect only
+//! replaces one that's already there if it's at least as strong, so weak hits can't cut a long
+//! stun short.
+//!
+//! Only enemies care about knockback, stuns and slows so far. Anything with [`Health`] burns.
+
+use crate::parrylord::attack::AttackPayload;
+use crate::parrylord::health::Health;
+use crate::parrylord::player::Player;
+use crate::screens::Screen;
+use crate::telemetry::{DamageTarget, TelemetryEvent};
+use crate::{AppSystems, PausableSystems};
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Knockback>();
+    app.register_type::<Stunned>();
+    app.register_type::<Slowed>();
+    app.register_type::<Burning>();
+
+    app.add_observer(apply_payload);
+
+    app.add_systems(
+        Update,
+        (
+            tick_status_effects.in_set(AppSystems::TickTimers),
+            (burn, expire_status_effects)
+                .chain()
+                .in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Triggered on something an attack hit.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct ApplyPayload {
+    pub payload: AttackPayload,
+    /// Which way the attack was going.
+    pub direction: Vec2,
+}
+
+/// Being pushed away by a hit. Nothing else moves the target until it's over.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Knockback(pub Timer);
+
+impl Knockback {
+    const SECS: f32 = 0.15;
+}
+
+/// Can't act. Stunning an enemy interrupts whatever it was doing.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Stunned(pub Timer);
+
+/// Moves and acts slower.
+#[derive(Component, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Slowed {
+    /// How much of its speed the target keeps.
+    pub factor: f32,
+    pub timer: Timer,
+}
+
+impl Slowed {
+    /// How much of its speed something keeps, slowed or not.
+    pub fn factor(slowed: Option<&Self>) -> f32 {
+        slowed.map_or(1.0, |it| it.factor)
+    }
+}
+
+/// Takes damage over time.
+#[derive(Component, Debug, Clone, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct Burning {
+    /// Damage taken every [`Self::TICK`] seconds.
+    pub damage: u32,
+    pub tick: Timer,
+    pub timer: Timer,
+}
+
+impl Burning {
+    pub const TICK: f32 = 0.5;
+}
+
+/// Filter for things that are free to act.
+pub type CanAct = (Without<Knockback>, Without<Stunned>);
+
+fn apply_payload(
+    trigger: Trigger<ApplyPayload>,
+    mut targets: Query<(
+        Option<&mut LinearVelocity>,
+        Option<&Stunned>,
+        Option<&Slowed>,
+        Option<&Burning>,
+    )>,
+    mut commands: Commands,
+) {
+    let ApplyPayload { payload, direction } = *trigger.event();
+    let Ok((velocity, stunned, slowed, burning)) = targets.get_mut(trigger.target()) else {
+        return;
+    };
+    let mut target = commands.entity(trigger.target());
+
+    if payload.knockback > 0.0 {
+        if let Some(mut velocity) = velocity {
+            velocity.0 = direction.normalize_or_zero() * payload.knockback;
+        }
+        target.try_insert(Knockback(Timer::from_seconds(
+            Knockback::SECS,
+            TimerMode::Once,
+        )));
+    }
+
+    if payload.stun_secs > 0.0
+        && stunned.is_none_or(|it| it.0.remaining_secs() <= payload.stun_secs)
+    {
+        target.try_insert(Stunned(Timer::from_seconds(
+            payload.stun_secs,
+            TimerMode::Once,
+        )));
+    }
+
+    let factor = 1.0 - payload.slow.clamp(0.0, 1.0);
+    if payload.slow > 0.0 && slowed.is_none_or(|it| it.factor >= factor) {
+        target.try_insert(Slowed {
+            factor,
+            timer: Timer::from_seconds(payload.slow_secs, TimerMode::Once),
+        });
+    }
+
+    if payload.burn > 0 && burning.is_none_or(|it| it.damage <= payload.burn) {
+        target.try_insert(Burning {
+            damage: payload.burn,
+            tick: Timer::from_seconds(Burning::TICK, TimerMode::Repeating),
+            timer: Timer::from_seconds(payload.burn_secs, TimerMode::Once),
+        });
+    }
+}
+
+fn tick_status_effects(
+    time: Res<Time>,
+    mut knockbacks: Query<&mut Knockback>,
+    mut stuns: Query<&mut Stunned>,
+    mut slows: Query<&mut Slowed>,
+    mut burns: Query<&mut Burning>,
+) {
+    for mut knockback in &mut knockbacks {
+        knockback.0.tick(time.delta());
+    }
+    for mut stunned in &mut stuns {
+        stunned.0.tick(time.delta());
+    }
+    for mut slowed in &mut slows {
+        slowed.timer.tick(time.delta());
+    }
+    for mut burning in &mut burns {
+        burning.tick.tick(time.delta());
+        burning.timer.tick(time.delta());
+    }
+}
+
+fn burn(
+    mut burning: Query<(&Burning, &mut Health, Has<Player>)>,
+    mut telemetry: EventWriter<TelemetryEvent>,
+) {
+    for (burning, mut health, is_player) in &mut burning {
+        if !burning.tick.just_finished() {
+            continue;
+        }
+
+        health.0 = health.0.saturating_sub(burning.damage);
+
+        telemetry.write(TelemetryEvent::Damage {
+            target: if is_player {
+                DamageTarget::Player
+            } else {
+                DamageTarget::Enemy
+            },
+            amount: burning.damage,
+            remaining_health: health.0,
+        });
+    }
+}
+
+fn expire_status_effects(
+    mut knockbacks: Query<(Entity, &Knockback, Option<&mut LinearVelocity>)>,
+    stuns: Query<(Entity, &Stunned)>,
+    slows: Query<(Entity, &Slowed)>,
+    burns: Query<(Entity, &Burning)>,
+    mut commands: Commands,
+) {
+    for (entity, knockback, velocity) in &mut knockbacks {
+        if knockback.0.finished() {
+            if let Some(mut velocity) = velocity {
+                *velocity = LinearVelocity::ZERO;
+            }
+            commands.entity(entity).try_remove::<Knockback>();
+        }
+    }
+    for (entity, stunned) in &stuns {
+        if stunned.0.finished() {
+            commands.entity(entity).try_remove::<Stunned>();
+        }
+    }
+    for (entity, slowed) in &slows {
+        if slowed.timer.finished() {
+            commands.entity(entity).try_remove::<Slowed>();
+        }
+    }
+    for (entity, burning) in &burns {
+        if burning.timer.finished() {
+            commands.entity(entity).try_remove::<Burning>();
+        }
+    }
+}
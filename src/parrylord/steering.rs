@@ -0,0 +1,210 @@
+//! Enemies moving with steering behaviours instead of at a fixed velocity.
+//!
+//! A [`Steering`] agent's [`SteeringBehaviour`] says where it wants to be going, and its velocity
+//! turns towards that a bit every frame. On top of that, agents keep their distance from each
+//! other, and run from the player when they're low on health.
+
+use crate::parrylord::health::{Health, MaxHealth};
+use crate::parrylord::navigation::NavGrid;
+use crate::parrylord::player::Player;
+use crate::parrylord::spawning::Spawning;
+use crate::parrylord::status::{CanAct, Slowed};
+use crate::screens::Screen;
+use crate::{AppSystems, PausableSystems};
+use avian2d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Steering>();
+
+    app.add_systems(
+        Update,
+        steer
+            .in_set(AppSystems::Update)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+pub enum SteeringBehaviour {
+    /// Stand still.
+    #[default]
+    Stop,
+    /// Go to a position, around walls, at full speed.
+    Seek(Vec2),
+    /// Go to a position, around walls, slowing down on the way in.
+    Arrive(Vec2),
+    /// Circle the player at a distance.
+    Orbit(f32),
+}
+
+#[derive(Component, Debug, Clone, PartialEq, Default, Reflect)]
+#[reflect(Component)]
+#[require(LinearVelocity)]
+pub struct Steering {
+    pub behaviour: SteeringBehaviour,
+    pub max_speed: f32,
+    /// Corners left to go through on the way to the [`SteeringBehaviour::Arrive`] goal.
+    path: Vec<Vec2>,
+    /// The goal `path` leads to.
+    path_goal: Option<Vec2>,
+}
+
+impl Steering {
+    /// How fast the velocity can change, in world units per second squared.
+    const MAX_FORCE: f32 = 1500.0;
+    /// How far from the goal agents start slowing down.
+    const ARRIVE_RADIUS: f32 = 150.0;
+    /// How close agents have to get to a corner of their path to head for the next one.
+    const WAYPOINT_RADIUS: f32 = 32.0;
+    const SEPARATION_RADIUS: f32 = 160.0;
+    const SEPARATION_WEIGHT: f32 = 1.5;
+    /// How fast agents orbit, as a fraction of their top speed.
+    const ORBIT_SPEED: f32 = 0.6;
+    /// Agents flee once they're down to `1 / FLEE_BELOW` of their health.
+    const FLEE_BELOW: u32 = 4;
+    /// How far ahead fleeing agents look for walls, in seconds.
+    const LOOKAHEAD: f32 = 0.3;
+
+    pub fn new(max_speed: f32) -> Self {
+        Self {
+            max_speed,
+            ..default()
+        }
+    }
+
+    /// The velocity the behaviour wants, before keeping away from other agents.
+    fn desired_velocity(
+        &mut self,
+        grid: &NavGrid,
+        position: Vec2,
+        velocity: Vec2,
+        player: Vec2,
+        fleeing: bool,
+    ) -> Vec2 {
+        if fleeing {
+            return self.flee(grid, position, player);
+        }
+
+        match self.behaviour {
+            SteeringBehaviour::Stop => Vec2::ZERO,
+            SteeringBehaviour::Seek(goal) => self.seek(grid, position, goal),
+            SteeringBehaviour::Arrive(goal) => self.arrive(grid, position, goal),
+            SteeringBehaviour::Orbit(radius) => self.orbit(position, velocity, player, radius),
+        }
+    }
+
+    /// The corner of the path to `goal` to head for next, and whether it's the goal itself.
+    fn next_waypoint(&mut self, grid: &NavGrid, position: Vec2, goal: Vec2) -> (Vec2, bool) {
+        if self.path_goal != Some(goal) {
+            // Straight at it if there's no way around.
+            self.path = grid.find_path(position, goal).unwrap_or_else(|| vec![goal]);
+            self.path_goal = Some(goal);
+        }
+
+        while self.path.len() > 1 && position.distance(self.path[0]) < Self::WAYPOINT_RADIUS {
+            self.path.remove(0);
+        }
+
+        (
+            self.path.first().copied().unwrap_or(goal),
+            self.path.len() <= 1,
+        )
+    }
+
+    fn seek(&mut self, grid: &NavGrid, position: Vec2, goal: Vec2) -> Vec2 {
+        let (target, _) = self.next_waypoint(grid, position, goal);
+        (target - position).normalize_or_zero() * self.max_speed
+    }
+
+    fn arrive(&mut self, grid: &NavGrid, position: Vec2, goal: Vec2) -> Vec2 {
+        let (target, is_goal) = self.next_waypoint(grid, position, goal);
+        let offset = target - position;
+        // Only slow down for the goal, not for corners.
+        let speed = if is_goal {
+            self.max_speed * (offset.length() / Self::ARRIVE_RADIUS).min(1.0)
+        } else {
+            self.max_speed
+        };
+
+        offset.normalize_or_zero() * speed
+    }
+
+    fn orbit(&self, position: Vec2, velocity: Vec2, player: Vec2, radius: f32) -> Vec2 {
+        let offset = position - player;
+        let outwards = offset.normalize_or(Vec2::X);
+
+        // Keep going around the way it's already going.
+        let mut tangent = outwards.perp();
+        if tangent.dot(velocity) < 0.0 {
+            tangent = -tangent;
+        }
+        let correction = ((radius - offset.length()) / Self::ARRIVE_RADIUS).clamp(-1.0, 1.0);
+
+        (tangent * Self::ORBIT_SPEED + outwards * correction).clamp_length_max(1.0) * self.max_speed
+    }
+
+    fn flee(&self, grid: &NavGrid, position: Vec2, player: Vec2) -> Vec2 {
+        let away = (position - player).normalize_or(Vec2::X) * self.max_speed;
+
+        // Slide along walls instead of running into them.
+        [away, away.perp(), -away.perp()]
+            .into_iter()
+            .find(|&it| !grid.is_blocked(position + it * Self::LOOKAHEAD))
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
+fn steer(
+    time: Res<Time>,
+    grid: Res<NavGrid>,
+    player: Query<&GlobalTransform, With<Player>>,
+    others: Query<(Entity, &GlobalTransform), With<Steering>>,
+    mut agents: Query<
+        (
+            Entity,
+            &mut Steering,
+            &GlobalTransform,
+            &mut LinearVelocity,
+            Option<&Slowed>,
+            Option<(&Health, &MaxHealth)>,
+        ),
+        (CanAct, Without<Spawning>),
+    >,
+) {
+    let Ok(player) = player.single() else {
+        return;
+    };
+    let player = player.translation().truncate();
+    let delta = time.delta_secs();
+
+    for (entity, mut steering, transform, mut velocity, slowed, health) in &mut agents {
+        let position = transform.translation().truncate();
+
+        if steering.behaviour == SteeringBehaviour::Stop {
+            velocity.0 = Vec2::ZERO;
+            continue;
+        }
+
+        let fleeing = health.is_some_and(|(health, max_health)| {
+            health.0.saturating_mul(Steering::FLEE_BELOW) <= max_health.0
+        });
+        let mut desired = steering.desired_velocity(&grid, position, velocity.0, player, fleeing);
+
+        let separation = others
+            .iter()
+            .filter(|&(other, _)| other != entity)
+            .map(|(_, other)| position - other.translation().truncate())
+            .filter(|offset| offset.length() < Steering::SEPARATION_RADIUS)
+            .map(|offset| {
+                offset.normalize_or_zero() * (1.0 - offset.length() / Steering::SEPARATION_RADIUS)
+            })
+            .sum::<Vec2>();
+        desired += separation * steering.max_speed * Steering::SEPARATION_WEIGHT;
+
+        let max_speed = steering.max_speed * Slowed::factor(slowed);
+        let force = (desired - velocity.0).clamp_length_max(Steering::MAX_FORCE * delta);
+        velocity.0 = (velocity.0 + force).clamp_length_max(max_speed);
+    }
+}
@@ -0,0 +1,157 @@
+//! Enemies winding up before a volley, so the player can see it coming.
+//!
+//! While an enemy has a [`Telegraph`], it reddens and an aim line grows towards where the first
+//! attack will go. Removing the [`Telegraph`], whether the volley went off or the enemy got
+//! interrupted, cleans both up.
+
+use crate::assets::AttackAssets;
+use crate::audio::SoundEffect;
+use crate::parrylord::status::Slowed;
+use crate::screens::Screen;
+use crate::{AppSystems, PausableSystems};
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use rand::prelude::SliceRandom;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Telegraph>();
+    app.register_type::<AimLine>();
+
+    app.add_observer(clean_up_telegraph);
+
+    app.add_systems(
+        Update,
+        (
+            tick_telegraphs.in_set(AppSystems::TickTimers),
+            show_telegraphs.in_set(AppSystems::Update),
+        )
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// An enemy winding up to attack `target`.
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Telegraph {
+    pub target: Vec2,
+    pub timer: Timer,
+    aim_line: Entity,
+}
+
+impl Telegraph {
+    /// How red enemies get right before they attack.
+    const TINT: Color = Color::srgb(1.0, 0.25, 0.25);
+    const LINE_COLOR: Color = Color::srgb(1.0, 0.1, 0.1);
+    const LINE_LENGTH: f32 = 400.0;
+    const LINE_WIDTH: f32 = 2.0;
+    const MAX_LINE_WIDTH: f32 = 8.0;
+    /// The wind-up sound is an attack sound, slowed down.
+    const SFX_SPEED: f32 = 0.5;
+    const SFX_VOLUME: f32 = 0.6;
+
+    /// Starts `enemy` winding up to attack `target` for `secs` seconds.
+    pub fn start(
+        commands: &mut Commands,
+        attack_assets: &AttackAssets,
+        enemy: Entity,
+        target: Vec2,
+        secs: f32,
+    ) {
+        let aim_line = commands
+            .spawn((
+                StateScoped(Screen::Gameplay),
+                Name::new("AimLine"),
+                AimLine,
+                Sprite {
+                    color: Self::LINE_COLOR.with_alpha(0.0),
+                    custom_size: Some(Vec2::ZERO),
+                    anchor: Anchor::CenterLeft,
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+
+        commands.entity(enemy).insert(Self {
+            target,
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+            aim_line,
+        });
+
+        commands.spawn((
+            AudioPlayer(
+                attack_assets
+                    .attack_sfx
+                    .choose(&mut rand::thread_rng())
+                    .expect("should exist")
+                    .clone(),
+            ),
+            PlaybackSettings::DESPAWN
+                .with_speed(Self::SFX_SPEED)
+                .with_volume(Volume::Linear(Self::SFX_VOLUME)),
+            SoundEffect,
+        ));
+    }
+
+    /// Whether the wind-up is over and the volley can go off.
+    pub fn is_finished(&self) -> bool {
+        self.timer.finished()
+    }
+}
+
+/// The line showing where a [`Telegraph`]ed attack will go.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component)]
+pub struct AimLine;
+
+fn tick_telegraphs(mut telegraphs: Query<(&mut Telegraph, Option<&Slowed>)>, time: Res<Time>) {
+    for (mut telegraph, slowed) in &mut telegraphs {
+        // Slowed enemies wind up slower too.
+        telegraph
+            .timer
+            .tick(time.delta().mul_f32(Slowed::factor(slowed)));
+    }
+}
+
+fn show_telegraphs(
+    mut enemies: Query<(&Telegraph, &GlobalTransform, &mut Sprite), Without<AimLine>>,
+    mut aim_lines: Query<(&mut Sprite, &mut Transform), With<AimLine>>,
+) {
+    for (telegraph, global_transform, mut sprite) in &mut enemies {
+        let charge = telegraph.timer.fraction();
+        sprite.color = Color::WHITE.mix(&Telegraph::TINT, charge);
+
+        let Ok((mut line, mut transform)) = aim_lines.get_mut(telegraph.aim_line) else {
+            continue;
+        };
+
+        let position = global_transform.translation().truncate();
+        let offset = telegraph.target - position;
+
+        line.color = Telegraph::LINE_COLOR.with_alpha(charge);
+        line.custom_size = Some(Vec2::new(
+            offset.length().min(Telegraph::LINE_LENGTH),
+            Telegraph::LINE_WIDTH.lerp(Telegraph::MAX_LINE_WIDTH, charge),
+        ));
+        // Under the enemy.
+        *transform = Transform::from_translation(position.extend(0.5))
+            .with_rotation(Quat::from_rotation_z(offset.to_angle()));
+    }
+}
+
+fn clean_up_telegraph(
+    trigger: Trigger<OnRemove, Telegraph>,
+    mut enemies: Query<(&Telegraph, &mut Sprite)>,
+    mut commands: Commands,
+) {
+    let Ok((telegraph, mut sprite)) = enemies.get_mut(trigger.target()) else {
+        return;
+    };
+
+    sprite.color = Color::WHITE;
+    if let Ok(mut aim_line) = commands.get_entity(telegraph.aim_line) {
+        aim_line.try_despawn();
+    }
+}
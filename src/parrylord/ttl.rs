@@ -1,3 +1,4 @@
+use crate::parrylord::projectile::{Projectile, ReleaseProjectile};
 use crate::screens::Screen;
 use crate::{AppSystems, PausableSystems};
 use bevy::prelude::*;
@@ -40,8 +41,17 @@ pub fn get_done_ttl_timers(timers: Query<(&Ttl, Entity)>) -> Vec<Entity> {
         .collect()
 }
 
-pub fn handle_done_ttl_timers(In(timers): In<Vec<Entity>>, mut commands: Commands) {
+pub fn handle_done_ttl_timers(
+    In(timers): In<Vec<Entity>>,
+    projectiles: Query<(), With<Projectile>>,
+    mut commands: Commands,
+) {
     for timer in timers {
+        if projectiles.contains(timer) {
+            commands.trigger_targets(ReleaseProjectile, timer);
+            continue;
+        }
+
         let Ok(mut entity) = commands.get_entity(timer) else {
             continue;
         };
@@ -42,14 +42,26 @@ pub fn plugin(app: &mut App) {
     );
 }
 
-fn unpause(mut next_pause: ResMut<NextState<Pause>>, mut physics: ResMut<Time<Physics>>) {
+// Virtual time is paused alongside physics so that time spent in the pause menu never reaches
+// gameplay, which keeps replays in sync.
+fn unpause(
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut physics: ResMut<Time<Physics>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
     next_pause.set(Pause(false));
     physics.unpause();
+    time.unpause();
 }
 
-fn pause(mut next_pause: ResMut<NextState<Pause>>, mut physics: ResMut<Time<Physics>>) {
+fn pause(
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut physics: ResMut<Time<Physics>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
     next_pause.set(Pause(true));
     physics.pause();
+    time.pause();
 }
 
 fn spawn_pause_overlay(mut commands: Commands) {
@@ -6,24 +6,20 @@ use bevy::{
     prelude::*,
 };
 
-use crate::{get_high_scores, screens::Screen, theme::prelude::*, AppSystems};
+use crate::{
+    leaderboard::connect_to_leaderboard,
+    screens::Screen,
+    theme::prelude::*,
+    zaphkiel::tween::{Tween, TweenCompleted, TweenTarget},
+    AppSystems,
+};
 
 pub fn plugin(app: &mut App) {
     // Spawn splash screen.
     app.insert_resource(ClearColor(SPLASH_BACKGROUND_COLOR));
     app.add_systems(
         OnEnter(Screen::Splash),
-        (spawn_splash_screen, get_high_scores),
-    );
-
-    // Animate splash screen.
-    app.add_systems(
-        Update,
-        (
-            tick_fade_in_out.in_set(AppSystems::TickTimers),
-            apply_fade_in_out.in_set(AppSystems::Update),
-        )
-            .run_if(in_state(Screen::Splash)),
+        (spawn_splash_screen, connect_to_leaderboard),
     );
 
     // Add splash timer.
@@ -52,68 +48,61 @@ const SPLASH_DURATION_SECS: f32 = 1.8;
 const SPLASH_FADE_DURATION_SECS: f32 = 0.6;
 
 fn spawn_splash_screen(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn((
-        widget::ui_root("Splash Screen"),
-        BackgroundColor(SPLASH_BACKGROUND_COLOR),
-        StateScoped(Screen::Splash),
-        children![(
-            Name::new("Splash image"),
-            Node {
-                margin: UiRect::all(Val::Auto),
-                width: Val::Percent(70.0),
-                ..default()
-            },
-            ImageNode::new(asset_server.load_with_settings(
-                // This should be an embedded asset for instant loading, but that is
-                // currently [broken on Windows Wasm builds](https://github.com/bevyengine/bevy/issues/14246).
-                "images/splash.png",
-                |settings: &mut ImageLoaderSettings| {
-                    // Make an exception for the splash image in case
-                    // `ImagePlugin::default_nearest()` is used for pixel art.
-                    settings.sampler = ImageSampler::linear();
-                },
-            )),
-            ImageNodeFadeInOut {
-                total_duration: SPLASH_DURATION_SECS,
-                fade_duration: SPLASH_FADE_DURATION_SECS,
-                t: 0.0,
-            },
-        )],
-    ));
-}
-
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-struct ImageNodeFadeInOut {
-    /// Total duration in seconds.
-    total_duration: f32,
-    /// Fade duration in seconds.
-    fade_duration: f32,
-    /// Current progress in seconds, between 0 and [`Self::total_duration`].
-    t: f32,
+    commands
+        .spawn((
+            widget::ui_root("Splash Screen"),
+            BackgroundColor(SPLASH_BACKGROUND_COLOR),
+            StateScoped(Screen::Splash),
+        ))
+        .with_children(|children_spawner| {
+            children_spawner
+                .spawn(splash_image(&asset_server))
+                .observe(fade_out_splash_image);
+        });
 }
 
-impl ImageNodeFadeInOut {
-    fn alpha(&self) -> f32 {
-        // Normalize by duration.
-        let t = (self.t / self.total_duration).clamp(0.0, 1.0);
-        let fade = self.fade_duration / self.total_duration;
-
-        // Regular trapezoid-shaped graph, flat at the top with alpha = 1.0.
-        ((1.0 - (2.0 * t - 1.0).abs()) / fade).min(1.0)
-    }
-}
-
-fn tick_fade_in_out(time: Res<Time>, mut animation_query: Query<&mut ImageNodeFadeInOut>) {
-    for mut anim in &mut animation_query {
-        anim.t += time.delta_secs();
-    }
+fn splash_image(asset_server: &AssetServer) -> impl Bundle {
+    (
+        Name::new("Splash image"),
+        Node {
+            margin: UiRect::all(Val::Auto),
+            width: Val::Percent(70.0),
+            ..default()
+        },
+        ImageNode::new(asset_server.load_with_settings(
+            // This should be an embedded asset for instant loading, but that is
+            // currently [broken on Windows Wasm builds](https://github.com/bevyengine/bevy/issues/14246).
+            "images/splash.png",
+            |settings: &mut ImageLoaderSettings| {
+                // Make an exception for the splash image in case
+                // `ImagePlugin::default_nearest()` is used for pixel art.
+                settings.sampler = ImageSampler::linear();
+            },
+        )),
+        Tween::new(
+            TweenTarget::ImageNodeAlpha {
+                start: 0.0,
+                end: 1.0,
+            },
+            SPLASH_FADE_DURATION_SECS,
+        ),
+    )
 }
 
-fn apply_fade_in_out(mut animation_query: Query<(&ImageNodeFadeInOut, &mut ImageNode)>) {
-    for (anim, mut image) in &mut animation_query {
-        image.color.set_alpha(anim.alpha());
-    }
+/// Fades the splash image back out once it's faded in, so it's gone by the end of the splash.
+fn fade_out_splash_image(trigger: Trigger<TweenCompleted>, mut commands: Commands) {
+    commands.entity(trigger.target()).insert(
+        Tween::new(
+            TweenTarget::ImageNodeAlpha {
+                start: 1.0,
+                end: 0.0,
+            },
+            SPLASH_FADE_DURATION_SECS,
+        )
+        .with_delay(SPLASH_DURATION_SECS - 2.0 * SPLASH_FADE_DURATION_SECS),
+    );
+    // Only once, the fade out completing triggers this too.
+    commands.entity(trigger.observer()).despawn();
 }
 
 #[derive(Resource, Debug, Clone, PartialEq, Reflect)]
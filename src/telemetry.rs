@@ -0,0 +1,241 @@
+//! An optional, machine-readable log of everything that happens during a run.
+//!
+//! Every [`TelemetryEvent`] is written as one JSON object per line (JSON Lines), so runs can be
+//! analysed offline to balance enemy health and attack counts.
+//! Logging only happens on native builds, and only while [`TelemetrySettings::enabled`] is set.
+
+use crate::screens::Screen;
+use crate::ParrylordSingleton;
+use bevy::prelude::*;
+use serde::Serialize;
+
+#[cfg(not(target_family = "wasm"))]
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub fn plugin(app: &mut App) {
+    app.add_event::<TelemetryEvent>();
+    app.register_type::<TelemetrySettings>();
+    app.init_resource::<TelemetrySettings>();
+
+    #[cfg(not(target_family = "wasm"))]
+    {
+        app.add_systems(OnExit(Screen::Gameplay), request_telemetry_finish);
+        app.add_systems(
+            Last,
+            (
+                write_telemetry_events,
+                finish_telemetry_log.run_if(resource_exists::<FinishTelemetryLog>),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Whether runs should be logged, and where the logs should go.
+#[derive(Resource, Debug, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Resource)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+    /// The directory every run's `.jsonl` file is created in.
+    pub directory: String,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: String::from("telemetry"),
+        }
+    }
+}
+
+/// Something worth recording about the current run.
+#[derive(Event, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TelemetryEvent {
+    RunStarted {
+        seed: u64,
+    },
+    LevelStarted {
+        level: u32,
+        enemies: u32,
+    },
+    EnemySpawned {
+        position: [f32; 2],
+        health: u32,
+        elite: bool,
+    },
+    Parry {
+        projectiles: u32,
+        power: u32,
+    },
+    Damage {
+        target: DamageTarget,
+        amount: u32,
+        remaining_health: u32,
+    },
+    EnemyKilled {
+        enemies_killed: u32,
+    },
+    VolleyCleared {
+        volleys_cleared: u32,
+    },
+    Rally {
+        length: u32,
+        longest_rally: u32,
+    },
+    PlayerDied,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DamageTarget {
+    Player,
+    Enemy,
+}
+
+/// A single line of the log.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Serialize)]
+struct TelemetryRecord<'a, T: Serialize> {
+    /// Seconds of (virtual) game time since startup.
+    t: f32,
+    /// Wall-clock time in milliseconds since the Unix epoch.
+    unix_ms: u128,
+    #[serde(flatten)]
+    event: &'a T,
+}
+
+/// The final line of every log, written when leaving gameplay.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Serialize)]
+#[serde(tag = "event", rename = "run_ended")]
+struct RunEnded<'a> {
+    #[serde(flatten)]
+    stats: &'a ParrylordSingleton,
+    score: u128,
+}
+
+/// The open log file for the current run, if any.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Resource)]
+struct TelemetryLog(BufWriter<File>);
+
+/// The run is over, so the log gets finished once the last of its events are written.
+#[cfg(not(target_family = "wasm"))]
+#[derive(Resource)]
+struct FinishTelemetryLog;
+
+#[cfg(not(target_family = "wasm"))]
+impl TelemetryLog {
+    fn create(settings: &TelemetrySettings, seed: u64) -> std::io::Result<Self> {
+        let directory = PathBuf::from(&settings.directory);
+        std::fs::create_dir_all(&directory)?;
+
+        let path = directory.join(format!("run-{}-{seed}.jsonl", unix_ms()));
+        info!("writing telemetry to {}", path.display());
+
+        Ok(Self(BufWriter::new(File::create(path)?)))
+    }
+
+    fn write<T: Serialize>(&mut self, t: f32, event: &T) {
+        let record = TelemetryRecord {
+            t,
+            unix_ms: unix_ms(),
+            event,
+        };
+
+        let result = serde_json::to_writer(&mut self.0, &record)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.0.write_all(b"\n"));
+
+        if let Err(e) = result {
+            error!(?e);
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn unix_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|it| it.as_millis())
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write_telemetry_events(
+    mut events: EventReader<TelemetryEvent>,
+    log: Option<ResMut<TelemetryLog>>,
+    settings: Res<TelemetrySettings>,
+    time: Res<Time<Virtual>>,
+    mut commands: Commands,
+) {
+    if !settings.enabled {
+        events.clear();
+        return;
+    }
+
+    let t = time.elapsed_secs();
+    let mut log = log.map(ResMut::into_inner);
+    let mut new_log = None;
+
+    for event in events.read() {
+        // A new run gets a new file.
+        if let &TelemetryEvent::RunStarted { seed } = event {
+            match TelemetryLog::create(&settings, seed) {
+                Ok(created) => {
+                    log = None;
+                    new_log = Some(created);
+                }
+                Err(e) => error!(?e),
+            }
+        }
+
+        if let Some(log) = new_log.as_mut().or(log.as_deref_mut()) {
+            log.write(t, event);
+        }
+    }
+
+    if let Some(new_log) = new_log {
+        commands.insert_resource(new_log);
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn request_telemetry_finish(mut commands: Commands) {
+    commands.insert_resource(FinishTelemetryLog);
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn finish_telemetry_log(
+    log: Option<ResMut<TelemetryLog>>,
+    singleton: Res<ParrylordSingleton>,
+    time: Res<Time<Virtual>>,
+    mut commands: Commands,
+) {
+    commands.remove_resource::<FinishTelemetryLog>();
+
+    let Some(mut log) = log else {
+        return;
+    };
+
+    log.write(
+        time.elapsed_secs(),
+        &RunEnded {
+            stats: &singleton,
+            score: singleton.calculate_score(),
+        },
+    );
+
+    if let Err(e) = log.0.flush() {
+        error!(?e);
+    }
+
+    commands.remove_resource::<TelemetryLog>();
+}
@@ -5,6 +5,8 @@
 
 pub mod interaction;
 pub mod palette;
+pub mod scroll;
+pub mod text_input;
 pub mod widget;
 
 #[allow(unused_imports)]
@@ -15,5 +17,5 @@ pub mod prelude {
 use bevy::prelude::*;
 
 pub fn plugin(app: &mut App) {
-    app.add_plugins(interaction::plugin);
+    app.add_plugins((interaction::plugin, scroll::plugin, text_input::plugin));
 }
@@ -3,6 +3,9 @@ use bevy::prelude::*;
 /// #ddd369
 pub const LABEL_TEXT: Color = Color::srgb(0.867, 0.827, 0.412);
 
+/// #8a8760
+pub const PLACEHOLDER_TEXT: Color = Color::srgb(0.541, 0.529, 0.376);
+
 /// #fcfbcc
 pub const HEADER_TEXT: Color = Color::srgb(0.988, 0.984, 0.800);
 
@@ -14,3 +17,6 @@ pub const BUTTON_BACKGROUND: Color = Color::srgb(0.275, 0.400, 0.750);
 pub const BUTTON_HOVERED_BACKGROUND: Color = Color::srgb(0.384, 0.600, 0.820);
 /// #3d4999
 pub const BUTTON_PRESSED_BACKGROUND: Color = Color::srgb(0.239, 0.286, 0.600);
+
+/// #3d4999cc
+pub const HIGHLIGHTED_ROW_BACKGROUND: Color = Color::srgba(0.239, 0.286, 0.600, 0.8);
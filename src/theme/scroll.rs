@@ -0,0 +1,37 @@
+//! Scrolling UI nodes with the mouse wheel.
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::picking::hover::HoverMap;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.add_systems(Update, scroll_hovered_nodes);
+}
+
+/// How far a line of mouse wheel scrolling moves, in logical pixels.
+const LINE_HEIGHT: f32 = 36.0;
+
+/// Scrolls every hovered node that has [`Overflow::scroll_y`], like the
+/// [`scroll_list`](super::widget::scroll_list) widget.
+fn scroll_hovered_nodes(
+    mut mouse_wheel: EventReader<MouseWheel>,
+    hover_map: Res<HoverMap>,
+    mut nodes: Query<(&Node, &mut ScrollPosition)>,
+) {
+    for event in mouse_wheel.read() {
+        let dy = match event.unit {
+            MouseScrollUnit::Line => event.y * LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+
+        for entity in hover_map.values().flat_map(|hits| hits.keys()) {
+            let Ok((node, mut scroll_position)) = nodes.get_mut(*entity) else {
+                continue;
+            };
+
+            if node.overflow.y == OverflowAxis::Scroll {
+                scroll_position.offset_y -= dy;
+            }
+        }
+    }
+}
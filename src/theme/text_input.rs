@@ -0,0 +1,245 @@
+//! Single-line text fields. See [`widget::text_input`](super::widget::text_input).
+//!
+//! Text comes from [`KeyboardInput`] and [`Ime`] events, so anything the keyboard layout or input
+//! method can type works. Only the focused field (see [`InputFocus`]) receives input; clicking a
+//! field focuses it, and Escape unfocuses it.
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+use bevy::window::{Ime, PrimaryWindow};
+
+use crate::theme::palette::{
+    BUTTON_BACKGROUND, BUTTON_HOVERED_BACKGROUND, LABEL_TEXT, PLACEHOLDER_TEXT,
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<TextInput>();
+    app.init_resource::<InputFocus>();
+    app.add_observer(focus_clicked_text_input);
+
+    app.add_systems(
+        Update,
+        (
+            edit_focused_text_input,
+            enable_ime,
+            update_text_input_display,
+        )
+            .chain(),
+    );
+}
+
+/// The state of a text field.
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct TextInput {
+    value: String,
+    /// Position of the cursor in `value`, in characters.
+    cursor: usize,
+    /// Text being composed with an input method, shown at the cursor.
+    preedit: String,
+    /// The most characters `value` can hold.
+    pub max_len: usize,
+    /// Shown while the field is empty.
+    pub placeholder: String,
+}
+
+impl TextInput {
+    pub fn new(placeholder: impl Into<String>, max_len: usize) -> Self {
+        Self {
+            max_len,
+            placeholder: placeholder.into(),
+            ..default()
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn clear(&mut self) {
+        self.value.clear();
+        self.cursor = 0;
+    }
+
+    /// Byte index of the character at `cursor`.
+    fn byte_index(&self, cursor: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(cursor)
+            .map_or(self.value.len(), |(idx, _)| idx)
+    }
+
+    /// Inserts `text` at the cursor, leaving out control characters and anything past `max_len`.
+    pub fn insert(&mut self, text: &str) {
+        let room = self.max_len.saturating_sub(self.value.chars().count());
+
+        for c in text.chars().filter(|c| !c.is_control()).take(room) {
+            let idx = self.byte_index(self.cursor);
+            self.value.insert(idx, c);
+            self.cursor += 1;
+        }
+    }
+
+    fn delete_backward(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.delete_forward();
+        }
+    }
+
+    fn delete_forward(&mut self) {
+        let idx = self.byte_index(self.cursor);
+        if idx < self.value.len() {
+            self.value.remove(idx);
+        }
+    }
+
+    fn move_cursor(&mut self, cursor: usize) {
+        self.cursor = cursor.min(self.value.chars().count());
+    }
+}
+
+/// Triggered on a [`TextInput`] when Enter is pressed while it's focused.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TextInputSubmit;
+
+/// The [`Text`] showing a [`TextInput`]'s value.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TextInputDisplay;
+
+fn focus_clicked_text_input(
+    trigger: Trigger<Pointer<Click>>,
+    text_inputs: Query<(), With<TextInput>>,
+    mut focus: ResMut<InputFocus>,
+) {
+    if text_inputs.contains(trigger.target()) {
+        focus.set(trigger.target());
+    }
+}
+
+fn edit_focused_text_input(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut ime: EventReader<Ime>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut focus: ResMut<InputFocus>,
+    mut text_inputs: Query<&mut TextInput>,
+    mut commands: Commands,
+) {
+    let Some(entity) = focus.get() else {
+        keyboard_input.clear();
+        ime.clear();
+        return;
+    };
+    let Ok(mut text_input) = text_inputs.get_mut(entity) else {
+        // The focused field is gone.
+        focus.clear();
+        return;
+    };
+
+    let shortcut = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+
+    for event in keyboard_input.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        let cursor = text_input.cursor;
+        match &event.logical_key {
+            Key::Backspace => text_input.delete_backward(),
+            Key::Delete => text_input.delete_forward(),
+            Key::ArrowLeft => text_input.move_cursor(cursor.saturating_sub(1)),
+            Key::ArrowRight => text_input.move_cursor(cursor + 1),
+            Key::Home => text_input.move_cursor(0),
+            Key::End => text_input.move_cursor(usize::MAX),
+            Key::Enter => commands.trigger_targets(TextInputSubmit, entity),
+            Key::Escape => focus.clear(),
+            Key::Character(c) if shortcut && c.eq_ignore_ascii_case("v") => {
+                if let Some(text) = paste() {
+                    text_input.insert(&text);
+                }
+            }
+            _ if shortcut => {}
+            _ => {
+                if let Some(text) = &event.text {
+                    text_input.insert(text);
+                }
+            }
+        }
+    }
+
+    for event in ime.read() {
+        match event {
+            Ime::Preedit { value, .. } => value.clone_into(&mut text_input.preedit),
+            Ime::Commit { value, .. } => {
+                text_input.preedit.clear();
+                text_input.insert(value);
+            }
+            Ime::Enabled { .. } | Ime::Disabled { .. } => text_input.preedit.clear(),
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn paste() -> Option<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .inspect_err(|e| warn!("couldn't paste: {e}"))
+        .ok()
+}
+
+/// Browsers only hand out the clipboard in their own paste events, which don't reach the game.
+#[cfg(target_family = "wasm")]
+const fn paste() -> Option<String> {
+    None
+}
+
+/// Input methods only send [`Ime`] events while they're enabled.
+fn enable_ime(focus: Res<InputFocus>, mut window: Single<&mut Window, With<PrimaryWindow>>) {
+    let enabled = focus.get().is_some();
+    if window.ime_enabled != enabled {
+        window.ime_enabled = enabled;
+    }
+}
+
+fn update_text_input_display(
+    focus: Res<InputFocus>,
+    mut text_inputs: Query<(Entity, &TextInput, &Children, &mut BackgroundColor)>,
+    mut displays: Query<(&mut Text, &mut TextColor), With<TextInputDisplay>>,
+) {
+    for (entity, text_input, children, mut background) in &mut text_inputs {
+        let focused = focus.get() == Some(entity);
+
+        let (value, value_color) =
+            if text_input.value.is_empty() && text_input.preedit.is_empty() && !focused {
+                (text_input.placeholder.clone(), PLACEHOLDER_TEXT)
+            } else {
+                let idx = text_input.byte_index(text_input.cursor);
+                let (before, after) = text_input.value.split_at(idx);
+                let cursor = if focused { "|" } else { "" };
+                let value = format!("{before}{}{cursor}{after}", text_input.preedit);
+                (value, LABEL_TEXT)
+            };
+
+        let mut displays = displays.iter_many_mut(children);
+        while let Some((mut text, mut color)) = displays.fetch_next() {
+            // Only touch the text when it changes, so it isn't laid out again every frame.
+            if text.0 != value {
+                text.0.clone_from(&value);
+            }
+            color.set_if_neq(TextColor(value_color));
+        }
+
+        background.set_if_neq(BackgroundColor(if focused {
+            BUTTON_HOVERED_BACKGROUND
+        } else {
+            BUTTON_BACKGROUND
+        }));
+    }
+}
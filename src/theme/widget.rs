@@ -7,7 +7,11 @@ use bevy::{
     prelude::*,
 };
 
-use crate::theme::{interaction::InteractionPalette, palette::*};
+use crate::theme::{
+    interaction::InteractionPalette,
+    palette::*,
+    text_input::{TextInput, TextInputDisplay},
+};
 
 /// A root UI node that fills the window and centers its content.
 pub fn ui_root(name: impl Into<Cow<'static, str>>) -> impl Bundle {
@@ -48,6 +52,60 @@ pub fn label(text: impl Into<String>) -> impl Bundle {
     )
 }
 
+/// A row of widgets, e.g. buttons that belong together.
+pub fn row() -> impl Bundle {
+    (
+        Name::new("Row"),
+        Node {
+            column_gap: Val::Px(20.0),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Pickable::IGNORE,
+    )
+}
+
+/// A column that can be scrolled with the mouse wheel once its content is taller than `height`.
+pub fn scroll_list(name: impl Into<Cow<'static, str>>, height: Val) -> impl Bundle {
+    (
+        Name::new(name),
+        Node {
+            height,
+            flex_direction: FlexDirection::Column,
+            overflow: Overflow::scroll_y(),
+            ..default()
+        },
+    )
+}
+
+/// A single-line text field. It only takes input while focused, see [`InputFocus`].
+///
+/// [`InputFocus`]: bevy::input_focus::InputFocus
+pub fn text_input(placeholder: impl Into<String>, max_len: usize) -> impl Bundle {
+    (
+        Name::new("Text Input"),
+        TextInput::new(placeholder, max_len),
+        Node {
+            width: Val::Px(500.0),
+            height: Val::Px(50.0),
+            padding: UiRect::horizontal(Val::Px(16.0)),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(BUTTON_BACKGROUND),
+        BorderRadius::all(Val::Px(8.0)),
+        children![(
+            Name::new("Text Input Text"),
+            Text::default(),
+            TextFont::from_font_size(32.0),
+            TextColor(LABEL_TEXT),
+            TextInputDisplay,
+            // Clicks on the text should focus the field.
+            Pickable::IGNORE,
+        )],
+    )
+}
+
 /// A large rounded button with text and an action defined as an [`Observer`].
 pub fn button<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -0,0 +1,11 @@
+//! Odds and ends that don't belong anywhere else.
+
+use bevy::prelude::*;
+
+pub mod profanity;
+pub mod smoothing;
+pub mod tween;
+
+pub fn plugin(app: &mut App) {
+    app.add_plugins((profanity::plugin, tween::plugin));
+}
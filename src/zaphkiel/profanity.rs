@@ -0,0 +1,412 @@
+//! Keeps offensive names off the leaderboard.
+//!
+//! Both the text and the word lists are normalised before matching: confusable characters and
+//! leetspeak are mapped to the letters they stand in for, and anything that isn't a letter or a
+//! digit separates words. Letters may repeat any number of times ("fuuuck"), and words spelled out
+//! across separators ("a.s.s", "A S S") or phrases spanning several words are matched as a whole.
+//!
+//! Longer blocked words also match inside other words, unless they're part of a word on the
+//! allow-list (so "Scunthorpe"-style names still get through).
+
+use crate::asset_tracking::LoadResource;
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use std::io;
+
+pub fn plugin(app: &mut App) {
+    app.init_asset::<WordList>();
+    app.init_asset_loader::<WordListLoader>();
+
+    app.register_type::<ProfanityAssets>();
+    app.load_resource::<ProfanityAssets>();
+
+    app.add_systems(
+        Update,
+        update_profanity_filter
+            .run_if(resource_added::<ProfanityAssets>.or(on_event::<AssetEvent<WordList>>)),
+    );
+}
+
+/// A list of words or phrases, loaded from a `.words` file with one entry per line. Blank lines
+/// and lines starting with `#` are ignored.
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct WordList(pub Vec<String>);
+
+impl WordList {
+    fn parse(text: &str) -> Self {
+        let words = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect();
+
+        Self(words)
+    }
+}
+
+#[derive(Default)]
+struct WordListLoader;
+
+impl AssetLoader for WordListLoader {
+    type Asset = WordList;
+    type Settings = ();
+    type Error = io::Error;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text =
+            String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        Ok(WordList::parse(&text))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["words"]
+    }
+}
+
+#[derive(Resource, Asset, Clone, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct ProfanityAssets {
+    #[dependency]
+    pub blocked: Handle<WordList>,
+    #[dependency]
+    pub allowed: Handle<WordList>,
+}
+
+impl FromWorld for ProfanityAssets {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            blocked: assets.load("profanity/blocked.words"),
+            allowed: assets.load("profanity/allowed.words"),
+        }
+    }
+}
+
+/// (Re)builds the [`ProfanityFilter`] once the word lists are loaded, and when they change.
+fn update_profanity_filter(
+    profanity_assets: Option<Res<ProfanityAssets>>,
+    word_lists: Res<Assets<WordList>>,
+    mut commands: Commands,
+) {
+    let Some(profanity_assets) = profanity_assets else {
+        return;
+    };
+    let (Some(blocked), Some(allowed)) = (
+        word_lists.get(&profanity_assets.blocked),
+        word_lists.get(&profanity_assets.allowed),
+    ) else {
+        return;
+    };
+
+    commands.insert_resource(ProfanityFilter::new(
+        blocked.0.iter().map(String::as_str),
+        allowed.0.iter().map(String::as_str),
+    ));
+}
+
+/// A normalised word, as runs of the same character, so "fuuuck" and "fuck" line up.
+type Runs = Vec<(char, usize)>;
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ProfanityFilter {
+    blocked: Vec<Runs>,
+    allowed: Vec<Runs>,
+}
+
+impl ProfanityFilter {
+    /// Blocked words at least this long (once normalised) also match inside other words. Shorter
+    /// ones are part of too many harmless words.
+    pub const MIN_EMBEDDED_LEN: usize = 5;
+
+    pub fn new<'a>(
+        blocked: impl IntoIterator<Item = &'a str>,
+        allowed: impl IntoIterator<Item = &'a str>,
+    ) -> Self {
+        Self {
+            blocked: compile(blocked),
+            allowed: compile(allowed),
+        }
+    }
+
+    pub fn is_profane(&self, text: &str) -> bool {
+        let tokens = tokens(text);
+
+        (0..tokens.len()).any(|start| {
+            (start + 1..=tokens.len()).any(|end| {
+                let window = &tokens[start..end];
+                let word = runs(&window.concat());
+
+                // A single word, or one spelled out a letter at a time.
+                let spelled_out = window.len() == 1 || window.iter().all(|it| it.len() == 1);
+
+                self.blocked.iter().any(|blocked| {
+                    if word.len() == blocked.len() && matches_at(&word, blocked, 0) {
+                        return true;
+                    }
+
+                    spelled_out && self.is_embedded(&word, blocked)
+                })
+            })
+        })
+    }
+
+    /// Whether `blocked` is somewhere inside `word`, outside of any allowed word.
+    fn is_embedded(&self, word: &[(char, usize)], blocked: &[(char, usize)]) -> bool {
+        let len = blocked.iter().map(|(_, count)| count).sum::<usize>();
+        if len < Self::MIN_EMBEDDED_LEN {
+            return false;
+        }
+
+        occurrences(word, blocked).any(|at| {
+            let span = at..at + blocked.len();
+            !self.allowed.iter().any(|allowed| {
+                occurrences(word, allowed)
+                    .any(|it| it <= span.start && span.end <= it + allowed.len())
+            })
+        })
+    }
+}
+
+/// Normalises a word list, dropping duplicates.
+fn compile<'a>(words: impl IntoIterator<Item = &'a str>) -> Vec<Runs> {
+    let mut compiled = words
+        .into_iter()
+        .map(|word| runs(&tokens(word).concat()))
+        .filter(|it| !it.is_empty())
+        .collect::<Vec<_>>();
+    compiled.sort();
+    compiled.dedup();
+    compiled
+}
+
+/// Where `pattern` starts in `word`.
+fn occurrences<'a>(
+    word: &'a [(char, usize)],
+    pattern: &'a [(char, usize)],
+) -> impl Iterator<Item = usize> + 'a {
+    (0..(word.len() + 1).saturating_sub(pattern.len())).filter(|&at| matches_at(word, pattern, at))
+}
+
+/// Whether `pattern` matches `word` starting at the run `at`, where every run of `word` has at
+/// least as many repeats as the one in `pattern`.
+fn matches_at(word: &[(char, usize)], pattern: &[(char, usize)], at: usize) -> bool {
+    word.get(at..at + pattern.len()).is_some_and(|runs| {
+        runs.iter()
+            .zip(pattern)
+            .all(|(&(c, count), &(expected, min))| c == expected && count >= min)
+    })
+}
+
+fn runs(word: &str) -> Runs {
+    let mut runs = Runs::new();
+    for c in word.chars() {
+        match runs.last_mut() {
+            Some((last, count)) if *last == c => *count += 1,
+            _ => runs.push((c, 1)),
+        }
+    }
+    runs
+}
+
+/// Splits normalised text into words.
+fn tokens(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+
+    for c in text.chars().flat_map(char::to_lowercase).map(normalise) {
+        match c {
+            Some(c) => token.push(c),
+            None if !token.is_empty() => tokens.push(std::mem::take(&mut token)),
+            None => {}
+        }
+    }
+
+    if !token.is_empty() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+/// The letter `c` is standing in for, or `None` if it's a separator.
+fn normalise(c: char) -> Option<char> {
+    // Fullwidth forms of ASCII.
+    let c = match c {
+        '\u{ff01}'..='\u{ff5e}' => char::from_u32(u32::from(c) - 0xfee0).unwrap_or(c),
+        _ => c,
+    };
+
+    let c = match c {
+        // Leetspeak.
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' | '+' => 't',
+        '8' => 'b',
+        '9' => 'g',
+
+        // Accented letters, and Cyrillic and Greek lookalikes.
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' | 'а' | 'α' => 'a',
+        'в' | 'β' => 'b',
+        'ç' | 'ć' | 'č' | 'с' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ė' | 'ę' | 'ě' | 'е' | 'ё' | 'ε' => 'e',
+        'ğ' => 'g',
+        'н' => 'h',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' | 'į' | 'ı' | 'і' | 'ї' | 'ι' => 'i',
+        'ј' => 'j',
+        'к' | 'κ' => 'k',
+        'ł' | 'ľ' => 'l',
+        'м' => 'm',
+        'ñ' | 'ń' | 'ň' | 'п' | 'η' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' | 'о' | 'ο' => 'o',
+        'р' | 'ρ' => 'p',
+        'ř' | 'г' => 'r',
+        'ś' | 'š' | 'ş' | 'ѕ' => 's',
+        'ť' | 'т' | 'τ' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' | 'ų' | 'υ' | 'μ' => 'u',
+        'ν' => 'v',
+        'ш' | 'ω' => 'w',
+        'х' | 'χ' => 'x',
+        'ý' | 'ÿ' | 'у' | 'γ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => c,
+    };
+
+    c.is_alphanumeric().then_some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(blocked: &[&str], allowed: &[&str]) -> ProfanityFilter {
+        ProfanityFilter::new(blocked.iter().copied(), allowed.iter().copied())
+    }
+
+    /// The filter built from the word lists the game ships with.
+    fn shipped() -> ProfanityFilter {
+        let blocked = WordList::parse(include_str!("../../assets/profanity/blocked.words"));
+        let allowed = WordList::parse(include_str!("../../assets/profanity/allowed.words"));
+        ProfanityFilter::new(
+            blocked.0.iter().map(String::as_str),
+            allowed.0.iter().map(String::as_str),
+        )
+    }
+
+    #[test]
+    fn word_lists_skip_comments_and_blank_lines() {
+        let list = WordList::parse("# comment\n\n  one  \ntwo words\n");
+        assert_eq!(list.0, ["one", "two words"]);
+    }
+
+    #[test]
+    fn phrases_match_across_words() {
+        let filter = filter(&["2 girls 1 cup"], &[]);
+
+        assert!(filter.is_profane("2 girls 1 cup"));
+        assert!(filter.is_profane("2girls1cup"));
+        assert!(filter.is_profane("xX 2_Girls_1_Cup Xx"));
+        assert!(!filter.is_profane("2 girls"));
+    }
+
+    #[test]
+    fn separators_between_letters_are_ignored() {
+        let filter = filter(&["ass"], &[]);
+
+        assert!(filter.is_profane("a.s.s"));
+        assert!(filter.is_profane("A S S"));
+        assert!(filter.is_profane("a-s_s"));
+        assert!(filter.is_profane("big A.S.S"));
+        assert!(!filter.is_profane("a s"));
+    }
+
+    #[test]
+    fn leetspeak_and_homoglyphs_are_normalised() {
+        let filter = filter(&["ass", "shit"], &[]);
+
+        assert!(filter.is_profane("@$$"));
+        assert!(filter.is_profane("4SS"));
+        assert!(filter.is_profane("sh1t"));
+        assert!(filter.is_profane("5h!7"));
+        // Cyrillic "а" and "ѕ".
+        assert!(filter.is_profane("\u{430}\u{455}\u{455}"));
+        // Fullwidth.
+        assert!(filter.is_profane("\u{ff53}\u{ff48}\u{ff49}\u{ff54}"));
+        assert!(filter.is_profane("shít"));
+    }
+
+    #[test]
+    fn repeated_letters_still_match() {
+        let filter = filter(&["fuck", "ass"], &[]);
+
+        assert!(filter.is_profane("fuuuuck"));
+        assert!(filter.is_profane("FFUUCCKK"));
+        assert!(filter.is_profane("aaasssss"));
+        // Fewer repeats than the blocked word isn't it.
+        assert!(!filter.is_profane("as"));
+    }
+
+    #[test]
+    fn allowed_words_keep_embedded_matches_clean() {
+        let filter = filter(&["balls", "cunt"], &["footballs"]);
+
+        assert!(!filter.is_profane("footballs"));
+        assert!(!filter.is_profane("FootballsFan"));
+        assert!(filter.is_profane("bigballs"));
+        // Too short to match inside other words at all.
+        assert!(!filter.is_profane("Scunthorpe"));
+    }
+
+    #[test]
+    fn shipped_lists_let_harmless_names_through() {
+        let filter = shipped();
+
+        for name in [
+            "Scunthorpe",
+            "Massachusetts",
+            "class",
+            "Dickson",
+            "peacocks",
+            "basement",
+            "Parrylord",
+            "Player 1",
+        ] {
+            assert!(!filter.is_profane(name), "{name} shouldn't be blocked");
+        }
+    }
+
+    #[test]
+    fn shipped_lists_block_profanity() {
+        let filter = shipped();
+
+        for name in ["fuck", "f.u.c.k", "sh1t", "a s s", "fuuuuuck"] {
+            assert!(filter.is_profane(name), "{name} should be blocked");
+        }
+    }
+
+    #[test]
+    fn only_long_enough_words_match_inside_others() {
+        let long = &"qwertyuiop"[..ProfanityFilter::MIN_EMBEDDED_LEN];
+        let short = &long[..long.len() - 1];
+
+        let long_filter = filter(&[long], &[]);
+        assert!(long_filter.is_profane(long));
+        assert!(long_filter.is_profane(&format!("xx{long}xx")));
+
+        let short_filter = filter(&[short], &[]);
+        assert!(short_filter.is_profane(short));
+        assert!(!short_filter.is_profane(&format!("xx{short}xx")));
+    }
+}
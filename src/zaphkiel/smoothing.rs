@@ -0,0 +1,170 @@
+//! Frame-rate independent smoothing and easing.
+//!
+//! Everything here is generic over [`Interpolate`], so the same smoothing works for scalars,
+//! vectors, rotations and colors.
+
+use bevy::math::VectorSpace;
+use bevy::prelude::*;
+
+/// The decay rate used for most smoothing in the game. Higher is snappier.
+pub const DEFAULT_DECAY: f32 = 16.0;
+
+/// Values that can be blended between.
+pub trait Interpolate: Copy {
+    /// Blends from `self` (`t = 0.0`) to `target` (`t = 1.0`).
+    fn interpolate(self, target: Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(self, target: Self, t: f32) -> Self {
+        FloatExt::lerp(self, target, t)
+    }
+}
+
+impl Interpolate for Vec2 {
+    fn interpolate(self, target: Self, t: f32) -> Self {
+        self.lerp(target, t)
+    }
+}
+
+impl Interpolate for Vec3 {
+    fn interpolate(self, target: Self, t: f32) -> Self {
+        self.lerp(target, t)
+    }
+}
+
+impl Interpolate for Quat {
+    /// Takes the shortest path, since `q` and `-q` are the same rotation.
+    fn interpolate(self, target: Self, t: f32) -> Self {
+        // `slerp` already flips `target` when the dot product is negative.
+        self.slerp(target, t)
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(self, target: Self, t: f32) -> Self {
+        self.mix(&target, t)
+    }
+}
+
+/// Moves `current` towards `target`, covering the same fraction of the remaining distance per
+/// second regardless of the frame rate.
+///
+/// After `1 / decay` seconds, about 37% of the distance is left.
+pub fn exponential_decay<T: Interpolate>(current: T, target: T, decay: f32, delta: f32) -> T {
+    current.interpolate(target, 1.0 - f32::exp(-decay * delta))
+}
+
+/// A critically damped spring: follows a moving target as fast as possible without overshooting,
+/// and keeps its momentum when the target changes (unlike [`exponential_decay`]).
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct Spring<T> {
+    pub velocity: T,
+    /// Roughly how long it takes to reach the target, in seconds.
+    pub smooth_time: f32,
+}
+
+impl<T: VectorSpace> Spring<T> {
+    pub fn new(smooth_time: f32) -> Self {
+        Self {
+            velocity: T::ZERO,
+            smooth_time,
+        }
+    }
+
+    /// Steps the spring from `current` towards `target`, returning the new value.
+    pub fn update(&mut self, current: T, target: T, delta: f32) -> T {
+        // Game Programming Gems 4, chapter 1.10.
+        let omega = 2.0 / self.smooth_time.max(0.0001);
+        let x = omega * delta;
+        let exp = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+
+        let change = current - target;
+        let temp = (self.velocity + change * omega) * delta;
+        self.velocity = (self.velocity - temp * omega) * exp;
+
+        target + (change + temp) * exp
+    }
+}
+
+/// Blends from `start` to `end` along an easing curve, with `t` clamped to `0.0..=1.0`.
+pub fn ease<T: Interpolate>(start: T, end: T, t: f32, ease_function: EaseFunction) -> T {
+    start.interpolate(end, ease_function.sample_clamped(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELTA: f32 = 1.0 / 60.0;
+
+    #[test]
+    fn exponential_decay_is_frame_rate_independent() {
+        let once = exponential_decay(0.0, 10.0, DEFAULT_DECAY, 2.0 * DELTA);
+        let twice = exponential_decay(
+            exponential_decay(0.0, 10.0, DEFAULT_DECAY, DELTA),
+            10.0,
+            DEFAULT_DECAY,
+            DELTA,
+        );
+        assert!((once - twice).abs() < 1e-5, "{once} != {twice}");
+
+        let once = exponential_decay(Vec2::ZERO, Vec2::new(3.0, -4.0), 30.0, 2.0 * DELTA);
+        let twice = exponential_decay(
+            exponential_decay(Vec2::ZERO, Vec2::new(3.0, -4.0), 30.0, DELTA),
+            Vec2::new(3.0, -4.0),
+            30.0,
+            DELTA,
+        );
+        assert!(once.abs_diff_eq(twice, 1e-5), "{once} != {twice}");
+    }
+
+    #[test]
+    fn quat_takes_the_shortest_path_across_the_hemisphere_flip() {
+        // The same rotation as `from_rotation_z(0.2)`, on the other side of the hypersphere.
+        let target = -Quat::from_rotation_z(0.2);
+        let halfway = Quat::IDENTITY.interpolate(target, 0.5);
+
+        let angle = halfway.angle_between(Quat::from_rotation_z(0.1));
+        assert!(angle < 1e-4, "went the long way round: {angle}");
+    }
+
+    #[test]
+    fn ease_hits_its_endpoints() {
+        for ease_function in [
+            EaseFunction::Linear,
+            EaseFunction::QuadraticIn,
+            EaseFunction::CubicOut,
+            EaseFunction::BackOut,
+            EaseFunction::ElasticInOut,
+        ] {
+            // Clamped outside of `0.0..=1.0`.
+            for (t, expected) in [(0.0, 2.0), (1.0, 5.0), (-1.0, 2.0), (2.0, 5.0)] {
+                let actual = ease(2.0, 5.0, t, ease_function);
+                // Elastic curves only get within about `2^-10` of their ends.
+                assert!(
+                    (actual - expected).abs() < 1e-3,
+                    "{ease_function:?} at {t}: {actual} != {expected}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn spring_settles_without_overshooting() {
+        let mut spring = Spring::new(0.2);
+        let mut value = 0.0_f32;
+
+        for _ in 0..120 {
+            let next = spring.update(value, 1.0, DELTA);
+            assert!(
+                next >= value,
+                "moved away from the target: {value} -> {next}"
+            );
+            assert!(next <= 1.0, "overshot: {next}");
+            value = next;
+        }
+
+        assert!((value - 1.0).abs() < 1e-3, "didn't settle: {value}");
+    }
+}
@@ -0,0 +1,272 @@
+//! Animates a property of an entity over time, for fades, pops and blinks.
+//!
+//! An entity has at most one [`Tween`]. It's removed once it's done, and [`TweenCompleted`] is
+//! triggered on the entity, so another can be started from an observer.
+
+use crate::zaphkiel::smoothing::ease;
+use crate::AppSystems;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Tween>();
+
+    app.add_systems(
+        Update,
+        (
+            tick_tweens.in_set(AppSystems::TickTimers),
+            apply_tweens.in_set(AppSystems::Update),
+        ),
+    );
+}
+
+/// What a [`Tween`] animates, and between which values.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub enum TweenTarget {
+    Translation {
+        start: Vec3,
+        end: Vec3,
+    },
+    Rotation {
+        start: Quat,
+        end: Quat,
+    },
+    Scale {
+        start: Vec3,
+        end: Vec3,
+    },
+    SpriteColor {
+        start: Color,
+        end: Color,
+    },
+    SpriteAlpha {
+        start: f32,
+        end: f32,
+    },
+    ImageNodeColor {
+        start: Color,
+        end: Color,
+    },
+    ImageNodeAlpha {
+        start: f32,
+        end: f32,
+    },
+    BackgroundColor {
+        start: Color,
+        end: Color,
+    },
+    TextColor {
+        start: Color,
+        end: Color,
+    },
+    /// The [`Node`]'s width and height, in logical pixels.
+    NodeSize {
+        start: Vec2,
+        end: Vec2,
+    },
+    /// Shown for the first `visible_fraction` of each cycle and hidden for the rest.
+    Blink {
+        visible_fraction: f32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum Repeat {
+    #[default]
+    Once,
+    /// Plays this many cycles in total.
+    Times(u32),
+    Forever,
+}
+
+#[derive(Component, Debug, Clone, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Tween {
+    pub target: TweenTarget,
+    pub ease: EaseFunction,
+    /// Length of one cycle, in seconds.
+    pub duration: f32,
+    pub repeat: Repeat,
+    /// Play every other cycle backwards.
+    pub yoyo: bool,
+    /// Keep going while the game is paused, for menus.
+    pub real_time: bool,
+    /// Seconds since the start of the first cycle (negative during the delay).
+    elapsed: f32,
+}
+
+impl Tween {
+    pub fn new(target: TweenTarget, duration: f32) -> Self {
+        Self {
+            target,
+            ease: EaseFunction::Linear,
+            duration,
+            repeat: Repeat::Once,
+            yoyo: false,
+            real_time: false,
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn with_ease(mut self, ease: EaseFunction) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn with_yoyo(mut self) -> Self {
+        self.yoyo = true;
+        self
+    }
+
+    /// Waits `delay` seconds before the first cycle.
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.elapsed = -delay;
+        self
+    }
+
+    pub fn with_real_time(mut self) -> Self {
+        self.real_time = true;
+        self
+    }
+
+    fn cycles(&self) -> Option<u32> {
+        match self.repeat {
+            Repeat::Once => Some(1),
+            Repeat::Times(times) => Some(times.max(1)),
+            Repeat::Forever => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.cycles()
+            .is_some_and(|cycles| self.elapsed >= cycles as f32 * self.duration)
+    }
+
+    /// Progress through the current cycle, from `0.0` to `1.0`, before easing.
+    fn progress(&self) -> f32 {
+        if self.duration <= 0.0 || self.is_finished() {
+            // Stop on the last frame of the last cycle.
+            let last_backwards = self.yoyo && self.cycles().is_some_and(|it| it % 2 == 0);
+            return if last_backwards { 0.0 } else { 1.0 };
+        }
+
+        let elapsed = self.elapsed.max(0.0);
+        let cycle = (elapsed / self.duration).floor();
+        let t = elapsed / self.duration - cycle;
+
+        if self.yoyo && cycle as u32 % 2 == 1 {
+            1.0 - t
+        } else {
+            t
+        }
+    }
+}
+
+/// Triggered on an entity when its [`Tween`] finishes, right after it's removed.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TweenCompleted;
+
+fn tick_tweens(time: Res<Time>, real_time: Res<Time<Real>>, mut tweens: Query<&mut Tween>) {
+    for mut tween in &mut tweens {
+        tween.elapsed += if tween.real_time {
+            real_time.delta_secs()
+        } else {
+            time.delta_secs()
+        };
+    }
+}
+
+fn apply_tweens(
+    mut tweens: Query<(
+        Entity,
+        &Tween,
+        Option<&mut Transform>,
+        Option<&mut Sprite>,
+        Option<&mut ImageNode>,
+        Option<&mut BackgroundColor>,
+        Option<&mut TextColor>,
+        Option<&mut Node>,
+        Option<&mut Visibility>,
+    )>,
+    mut commands: Commands,
+) {
+    for (entity, tween, transform, sprite, image, background, text_color, node, visibility) in
+        &mut tweens
+    {
+        let t = tween.progress();
+
+        match tween.target {
+            TweenTarget::Translation { start, end } => {
+                if let Some(mut transform) = transform {
+                    transform.translation = ease(start, end, t, tween.ease);
+                }
+            }
+            TweenTarget::Rotation { start, end } => {
+                if let Some(mut transform) = transform {
+                    transform.rotation = ease(start, end, t, tween.ease);
+                }
+            }
+            TweenTarget::Scale { start, end } => {
+                if let Some(mut transform) = transform {
+                    transform.scale = ease(start, end, t, tween.ease);
+                }
+            }
+            TweenTarget::SpriteColor { start, end } => {
+                if let Some(mut sprite) = sprite {
+                    sprite.color = ease(start, end, t, tween.ease);
+                }
+            }
+            TweenTarget::SpriteAlpha { start, end } => {
+                if let Some(mut sprite) = sprite {
+                    sprite.color.set_alpha(ease(start, end, t, tween.ease));
+                }
+            }
+            TweenTarget::ImageNodeColor { start, end } => {
+                if let Some(mut image) = image {
+                    image.color = ease(start, end, t, tween.ease);
+                }
+            }
+            TweenTarget::ImageNodeAlpha { start, end } => {
+                if let Some(mut image) = image {
+                    image.color.set_alpha(ease(start, end, t, tween.ease));
+                }
+            }
+            TweenTarget::BackgroundColor { start, end } => {
+                if let Some(mut background) = background {
+                    background.0 = ease(start, end, t, tween.ease);
+                }
+            }
+            TweenTarget::TextColor { start, end } => {
+                if let Some(mut text_color) = text_color {
+                    text_color.0 = ease(start, end, t, tween.ease);
+                }
+            }
+            TweenTarget::NodeSize { start, end } => {
+                if let Some(mut node) = node {
+                    let size = ease(start, end, t, tween.ease);
+                    node.width = Val::Px(size.x);
+                    node.height = Val::Px(size.y);
+                }
+            }
+            TweenTarget::Blink { visible_fraction } => {
+                if let Some(mut visibility) = visibility {
+                    visibility.set_if_neq(if ease(0.0, 1.0, t, tween.ease) < visible_fraction {
+                        Visibility::Inherited
+                    } else {
+                        Visibility::Hidden
+                    });
+                }
+            }
+        }
+
+        if tween.is_finished() {
+            // Removed before triggering, so observers can start another tween.
+            commands.entity(entity).remove::<Tween>();
+            commands.trigger_targets(TweenCompleted, entity);
+        }
+    }
+}